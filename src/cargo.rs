@@ -0,0 +1,520 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of a Cargo build target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    Bin,
+    Lib,
+    Test,
+    Example,
+    Bench,
+}
+
+impl TargetKind {
+    /// The kind's name, which is also how Cargo reports bin, test, example
+    /// and bench targets
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TargetKind::Bin => "bin",
+            TargetKind::Lib => "lib",
+            TargetKind::Test => "test",
+            TargetKind::Example => "example",
+            TargetKind::Bench => "bench",
+        }
+    }
+}
+
+/// A single build target of a package
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub name: String,
+    pub kind: TargetKind,
+    /// The kind as Cargo reports it, e.g. `cdylib` or `proc-macro` for a
+    /// library, which a CodeLLDB cargo `filter` has to match
+    pub cargo_kind: String,
+}
+
+/// A Cargo package together with its build targets
+#[derive(Debug, Clone)]
+pub struct Package {
+    pub name: String,
+    /// Directory containing the package's Cargo.toml
+    pub root: PathBuf,
+    pub targets: Vec<Target>,
+}
+
+impl Package {
+    /// Returns the targets of the given kind
+    pub fn targets_of(&self, kind: TargetKind) -> impl Iterator<Item = &Target> {
+        self.targets.iter().filter(move |t| t.kind == kind)
+    }
+}
+
+/// Loads the packages of the Cargo project rooted at `root`, including every
+/// member of a workspace.
+///
+/// Prefers `cargo metadata`, which knows about every target Cargo would
+/// build, and falls back to reading Cargo.toml and the conventional source
+/// layout when cargo is unavailable or fails.
+pub fn load_packages(root: &Path) -> Vec<Package> {
+    if !root.join("Cargo.toml").is_file() {
+        return Vec::new();
+    }
+    load_from_metadata(root).unwrap_or_else(|| load_from_manifests(root))
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+}
+
+#[derive(Deserialize)]
+struct MetadataPackage {
+    name: String,
+    manifest_path: PathBuf,
+    targets: Vec<MetadataTarget>,
+}
+
+#[derive(Deserialize)]
+struct MetadataTarget {
+    name: String,
+    kind: Vec<String>,
+}
+
+fn load_from_metadata(root: &Path) -> Option<Vec<Package>> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .current_dir(root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let metadata: Metadata = serde_json::from_slice(&output.stdout).ok()?;
+
+    Some(
+        metadata
+            .packages
+            .into_iter()
+            .map(|package| Package {
+                name: package.name,
+                root: package
+                    .manifest_path
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default(),
+                targets: package
+                    .targets
+                    .into_iter()
+                    .filter_map(|target| {
+                        let (kind, cargo_kind) =
+                            target.kind.into_iter().find_map(|k| match k.as_str() {
+                                "bin" => Some((TargetKind::Bin, k)),
+                                "test" => Some((TargetKind::Test, k)),
+                                "example" => Some((TargetKind::Example, k)),
+                                "bench" => Some((TargetKind::Bench, k)),
+                                "lib" | "rlib" | "dylib" | "cdylib" | "staticlib"
+                                | "proc-macro" => Some((TargetKind::Lib, k)),
+                                _ => None,
+                            })?;
+                        Some(Target {
+                            name: target.name,
+                            kind,
+                            cargo_kind,
+                        })
+                    })
+                    .collect(),
+            })
+            .collect(),
+    )
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Option<ManifestPackage>,
+    workspace: Option<ManifestWorkspace>,
+    lib: Option<ManifestTarget>,
+    #[serde(default)]
+    bin: Vec<ManifestTarget>,
+    #[serde(default)]
+    test: Vec<ManifestTarget>,
+    #[serde(default)]
+    example: Vec<ManifestTarget>,
+    #[serde(default)]
+    bench: Vec<ManifestTarget>,
+}
+
+#[derive(Deserialize)]
+struct ManifestPackage {
+    name: String,
+    autobins: Option<bool>,
+    autotests: Option<bool>,
+    autoexamples: Option<bool>,
+    autobenches: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct ManifestWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct ManifestTarget {
+    name: Option<String>,
+    path: Option<PathBuf>,
+    #[serde(rename = "crate-type", alias = "crate_type")]
+    crate_type: Option<Vec<String>>,
+    #[serde(rename = "proc-macro", alias = "proc_macro")]
+    proc_macro: Option<bool>,
+}
+
+fn read_manifest(dir: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    toml::from_str(&content).ok()
+}
+
+/// Reads the root manifest and, for a workspace, the manifests of all
+/// members matched by `members` and not listed in `exclude`
+fn load_from_manifests(root: &Path) -> Vec<Package> {
+    let Some(mut manifest) = read_manifest(root) else {
+        return Vec::new();
+    };
+    let workspace = manifest.workspace.take();
+    let mut packages: Vec<Package> = package_from_manifest(root, manifest).into_iter().collect();
+
+    if let Some(workspace) = workspace {
+        let excluded: Vec<glob::Pattern> = workspace
+            .exclude
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(pattern.trim_end_matches('/')).ok())
+            .collect();
+        for member in &workspace.members {
+            let pattern = root.join(member.trim_end_matches('/'));
+            let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+                continue;
+            };
+            for dir in paths.filter_map(|p| p.ok()) {
+                let relative = dir.strip_prefix(root).unwrap_or(&dir);
+                if excluded.iter().any(|p| p.matches_path(relative))
+                    || packages.iter().any(|p| p.root == dir)
+                {
+                    continue;
+                }
+                if let Some(package) =
+                    read_manifest(&dir).and_then(|m| package_from_manifest(&dir, m))
+                {
+                    packages.push(package);
+                }
+            }
+        }
+    }
+    packages
+}
+
+/// Builds a package from its manifest, following Cargo's target auto-discovery
+fn package_from_manifest(root: &Path, manifest: Manifest) -> Option<Package> {
+    let package = manifest.package?;
+    let mut targets = Vec::new();
+
+    if manifest.lib.is_some() || root.join("src/lib.rs").is_file() {
+        let lib = manifest.lib.unwrap_or_default();
+        // Cargo reports a library by its first crate type
+        let cargo_kind = if lib.proc_macro == Some(true) {
+            "proc-macro".to_string()
+        } else {
+            lib.crate_type
+                .and_then(|types| types.into_iter().next())
+                .unwrap_or_else(|| "lib".to_string())
+        };
+        targets.push(Target {
+            name: lib.name.unwrap_or_else(|| package.name.replace('-', "_")),
+            kind: TargetKind::Lib,
+            cargo_kind,
+        });
+    }
+
+    let mut main = Vec::new();
+    let main_path = root.join("src/main.rs");
+    if main_path.is_file() {
+        main.push((package.name.clone(), main_path));
+    }
+    main.extend(discover_targets(&root.join("src/bin")));
+
+    let kinds = [
+        (TargetKind::Bin, manifest.bin, package.autobins, main),
+        (
+            TargetKind::Test,
+            manifest.test,
+            package.autotests,
+            discover_targets(&root.join("tests")),
+        ),
+        (
+            TargetKind::Example,
+            manifest.example,
+            package.autoexamples,
+            discover_targets(&root.join("examples")),
+        ),
+        (
+            TargetKind::Bench,
+            manifest.bench,
+            package.autobenches,
+            discover_targets(&root.join("benches")),
+        ),
+    ];
+    for (kind, declared, auto, discovered) in kinds {
+        // Cargo drops a discovered target whose file a declared one already uses
+        let declared_paths: Vec<PathBuf> = declared
+            .iter()
+            .filter_map(|t| t.path.as_ref())
+            .map(|path| normalize(&root.join(path)))
+            .collect();
+        let mut names: Vec<String> = declared.into_iter().filter_map(|t| t.name).collect();
+        if auto != Some(false) {
+            names.extend(
+                discovered
+                    .into_iter()
+                    .filter(|(_, path)| !declared_paths.contains(&normalize(path)))
+                    .map(|(name, _)| name),
+            );
+        }
+        names.sort();
+        names.dedup();
+        targets.extend(names.into_iter().map(|name| Target {
+            name,
+            kind,
+            cargo_kind: kind.as_str().to_string(),
+        }));
+    }
+
+    Some(Package {
+        name: package.name,
+        root: root.to_path_buf(),
+        targets,
+    })
+}
+
+/// Lists auto-discovered targets in a directory with their source files:
+/// `<name>.rs` files and `<name>/main.rs` subdirectories
+fn discover_targets(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                let name = path.file_stem()?.to_string_lossy().to_string();
+                Some((name, path))
+            } else if path.join("main.rs").is_file() {
+                let name = path.file_name()?.to_string_lossy().to_string();
+                Some((name, path.join("main.rs")))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Drops `.` components so that declared and discovered paths compare equal
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory below the system temp dir, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "vscode-launch-gen-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+
+        fn write(&self, path: &str, content: &str) {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn targets(package: &Package) -> Vec<(TargetKind, &str, &str)> {
+        let mut targets: Vec<_> = package
+            .targets
+            .iter()
+            .map(|t| (t.kind, t.name.as_str(), t.cargo_kind.as_str()))
+            .collect();
+        targets.sort_by_key(|(kind, name, _)| (kind.as_str(), *name));
+        targets
+    }
+
+    #[test]
+    fn targets_are_discovered_from_the_layout() {
+        let dir = TempDir::new("discover");
+        dir.write("Cargo.toml", "[package]\nname = \"my-app\"\n");
+        for file in [
+            "src/lib.rs",
+            "src/main.rs",
+            "src/bin/extra.rs",
+            "src/bin/nested/main.rs",
+            "src/bin/nested/helper.rs",
+            "tests/it.rs",
+            "examples/demo.rs",
+            "benches/speed.rs",
+        ] {
+            dir.write(file, "");
+        }
+
+        let packages = load_from_manifests(&dir.0);
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "my-app");
+        assert_eq!(
+            targets(&packages[0]),
+            [
+                (TargetKind::Bench, "speed", "bench"),
+                (TargetKind::Bin, "extra", "bin"),
+                (TargetKind::Bin, "my-app", "bin"),
+                (TargetKind::Bin, "nested", "bin"),
+                (TargetKind::Example, "demo", "example"),
+                (TargetKind::Lib, "my_app", "lib"),
+                (TargetKind::Test, "it", "test"),
+            ]
+        );
+    }
+
+    #[test]
+    fn autobins_false_keeps_only_declared_bins() {
+        let dir = TempDir::new("autobins");
+        dir.write(
+            "Cargo.toml",
+            "[package]\nname = \"app\"\nautobins = false\n\n[[bin]]\nname = \"declared\"\n",
+        );
+        dir.write("src/main.rs", "");
+        dir.write("src/bin/other.rs", "");
+        dir.write("tests/it.rs", "");
+
+        let packages = load_from_manifests(&dir.0);
+        assert_eq!(
+            targets(&packages[0]),
+            [
+                (TargetKind::Bin, "declared", "bin"),
+                (TargetKind::Test, "it", "test"),
+            ]
+        );
+    }
+
+    #[test]
+    fn declared_path_replaces_the_discovered_target() {
+        let dir = TempDir::new("declared-path");
+        dir.write(
+            "Cargo.toml",
+            "[package]\nname = \"fb\"\n\n[[bin]]\nname = \"tool\"\npath = \"./src/main.rs\"\n\n\
+             [[example]]\nname = \"renamed\"\npath = \"examples/demo.rs\"\n",
+        );
+        dir.write("src/main.rs", "");
+        dir.write("examples/demo.rs", "");
+        dir.write("examples/other/main.rs", "");
+
+        let packages = load_from_manifests(&dir.0);
+        assert_eq!(
+            targets(&packages[0]),
+            [
+                (TargetKind::Bin, "tool", "bin"),
+                (TargetKind::Example, "other", "example"),
+                (TargetKind::Example, "renamed", "example"),
+            ]
+        );
+    }
+
+    #[test]
+    fn library_kind_follows_crate_type() {
+        let dir = TempDir::new("crate-type");
+        dir.write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"ffi\", \"macros\"]\n",
+        );
+        dir.write(
+            "ffi/Cargo.toml",
+            "[package]\nname = \"ffi\"\n\n[lib]\ncrate-type = [\"cdylib\", \"rlib\"]\n",
+        );
+        dir.write("ffi/src/lib.rs", "");
+        dir.write(
+            "macros/Cargo.toml",
+            "[package]\nname = \"macros\"\n\n[lib]\nproc-macro = true\n",
+        );
+        dir.write("macros/src/lib.rs", "");
+
+        let packages = load_from_manifests(&dir.0);
+        let kinds: Vec<(&str, Vec<_>)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), targets(p)))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("ffi", vec![(TargetKind::Lib, "ffi", "cdylib")]),
+                ("macros", vec![(TargetKind::Lib, "macros", "proc-macro")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn workspace_members_follow_globs_and_exclude() {
+        let dir = TempDir::new("workspace");
+        dir.write(
+            "Cargo.toml",
+            "[package]\nname = \"root\"\n\n[workspace]\nmembers = [\"crates/*\", \"tools/cli/\"]\n\
+             exclude = [\"crates/old\"]\n",
+        );
+        dir.write("src/main.rs", "");
+        for member in ["crates/a", "crates/b", "crates/old", "tools/cli"] {
+            let name = member.rsplit('/').next().unwrap();
+            dir.write(
+                &format!("{}/Cargo.toml", member),
+                &format!("[package]\nname = \"{}\"\n", name),
+            );
+            dir.write(&format!("{}/src/lib.rs", member), "");
+        }
+        // Matched by the glob but not a package
+        dir.write("crates/notes/README.md", "");
+
+        let mut names: Vec<String> = load_from_manifests(&dir.0)
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["a", "b", "cli", "root"]);
+    }
+
+    #[test]
+    fn missing_manifest_gives_no_packages() {
+        let dir = TempDir::new("missing");
+        assert!(load_packages(&dir.0).is_empty());
+        assert!(load_from_manifests(&dir.0).is_empty());
+    }
+}
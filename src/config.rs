@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::launch::LaunchConfig;
+
+/// Name of the project configuration file, looked up in the project root
+pub const CONFIG_FILE: &str = ".vscode-launch-gen.toml";
+
+/// Project-level defaults, read from [`CONFIG_FILE`].
+///
+/// Command line flags take precedence: `--output` replaces `output`, and a
+/// `--type` for a provider replaces the entry for the same provider in `types`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Output file, relative to the project root
+    pub output: Option<PathBuf>,
+    /// Run detection as if `--detect` was given
+    pub detect: bool,
+    /// Set Python interpreters found outside the project, e.g. in Poetry's
+    /// cache or a conda installation. Their absolute paths only hold on this
+    /// machine, so by default only environments inside the project are used.
+    pub external_python: bool,
+    /// Types to always generate, in the same form as `--type`
+    pub types: Vec<String>,
+    /// Detected types to leave out
+    pub disable: Vec<String>,
+    /// Parameters per provider, in the `key=value,...` form of `--type`;
+    /// parameters given on the command line take precedence
+    pub params: BTreeMap<String, String>,
+    /// Changes applied to generated configurations, keyed by configuration name
+    pub overrides: BTreeMap<String, Override>,
+}
+
+/// Properties replacing those of a generated configuration
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct Override {
+    pub args: Option<Vec<String>>,
+    /// Added to the configuration's environment, replacing variables with the
+    /// same name
+    pub env: BTreeMap<String, String>,
+    pub cwd: Option<String>,
+    pub pre_launch_task: Option<String>,
+}
+
+impl Config {
+    /// Reads the config file given with `--config`, or [`CONFIG_FILE`] in the
+    /// project root if it exists
+    pub fn load(root: &Path, path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let default_path = root.join(CONFIG_FILE);
+        let path = match path {
+            Some(path) => path,
+            None if default_path.is_file() => &default_path,
+            None => return Ok(Config::default()),
+        };
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e).into())
+    }
+
+    /// Whether a detected type is switched off. Entries without a parameter
+    /// match every parameter of the provider.
+    pub fn is_disabled(&self, type_name: &str) -> bool {
+        self.disable
+            .iter()
+            .any(|d| d == type_name || type_name.split(':').next() == Some(d.as_str()))
+    }
+
+    /// Applies the matching override to a configuration
+    pub fn apply_overrides(&self, config: &mut LaunchConfig) {
+        let Some(over) = self.overrides.get(&config.name) else {
+            return;
+        };
+        if let Some(args) = &over.args {
+            config.args = Some(args.clone());
+        }
+        if !over.env.is_empty() && !config.extend_env(&over.env) {
+            eprintln!(
+                "Warning: Configuration '{}' does not support environment variables",
+                config.name
+            );
+        }
+        if let Some(cwd) = &over.cwd {
+            config.cwd = Some(cwd.clone());
+        }
+        if let Some(task) = &over.pre_launch_task {
+            config.pre_launch_task = Some(task.clone());
+        }
+    }
+
+    /// Names of overrides that match none of the configurations
+    pub fn unused_overrides<'a>(&'a self, configs: &[LaunchConfig]) -> Vec<&'a str> {
+        self.overrides
+            .keys()
+            .filter(|name| !configs.iter().any(|c| &c.name == *name))
+            .map(String::as_str)
+            .collect()
+    }
+}
@@ -1,188 +1,453 @@
-use std::{collections::HashMap, fs};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
 
+use serde::Serialize;
 use serde_json::Value;
-use walkdir::WalkDir;
 
+use crate::cargo::TargetKind;
+use crate::project::Project;
+use crate::python;
 use crate::types::ConfigProvider;
 
-pub fn detect_project_types(
-    providers: &[Box<dyn ConfigProvider>],
-) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    let mut detected_types = Vec::new();
-    let mut detected_files = HashMap::new();
-    let mut has_python_files = false;
-    let mut has_js_files = false;
-    let mut has_ts_files = false;
-    let mut has_rust_files = false;
-    let mut has_cpp_files = false;
-
-    // Scan files for detection
-    for entry in WalkDir::new(".")
-        .max_depth(2)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() {
-            let file_name = path
-                .file_name()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
-
-            // Check file extensions for quick language detection
-            if let Some(ext) = path.extension() {
-                let ext_str = ext.to_string_lossy();
-                match ext_str.as_ref() {
-                    "py" => has_python_files = true,
-                    "js" => has_js_files = true,
-                    "ts" => has_ts_files = true,
-                    "rs" => has_rust_files = true,
-                    "cpp" | "cc" | "cxx" | "h" | "hpp" => has_cpp_files = true,
-                    _ => {}
-                }
-            }
+/// Evidence items kept per detected type; further ones are only counted
+const MAX_EVIDENCE: usize = 5;
 
-            // Store special files for content-based detection
-            if file_name == "requirements.txt"
-                || file_name == "package.json"
-                || file_name == "Cargo.toml"
-                || file_name == "CMakeLists.txt"
-                || file_name == "Makefile"
-            {
-                detected_files.insert(file_name.clone(), path.to_path_buf());
-            }
+/// How sure detection is that a project type applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    /// Guessed from the absence of something, often wrong
+    Low,
+    /// Inferred from file names, extensions or content patterns
+    Medium,
+    /// Declared in a manifest
+    High,
+}
 
-            // Check each provider for file-based detection
-            for provider in providers.iter() {
-                if provider.can_detect_from_file(path) {
-                    detected_types.push(provider.name().to_string());
-                }
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Confidence::Low => "low",
+            Confidence::Medium => "medium",
+            Confidence::High => "high",
+        })
+    }
+}
+
+/// Why a project type was detected. Paths are relative to the project root.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "source", rename_all = "kebab-case")]
+pub enum Evidence {
+    /// A file matched by name or extension
+    File { path: PathBuf },
+    /// A file whose content matched a pattern
+    Content { path: PathBuf, pattern: String },
+    /// A dependency declared in a manifest
+    Dependency { manifest: PathBuf, name: String },
+    /// A manifest section, or the lack of one
+    ManifestKey { manifest: PathBuf, key: String },
+    /// A build target reported for a Cargo package
+    CargoTarget {
+        package: String,
+        target: String,
+        kind: TargetKind,
+    },
+    /// A manifest whose content a provider recognized
+    Recognized { manifest: PathBuf, provider: String },
+    /// Follows from other detected types
+    Implied { types: Vec<String> },
+}
+
+impl fmt::Display for Evidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Evidence::File { path } => write!(f, "file {}", path.display()),
+            Evidence::Content { path, pattern } => {
+                write!(f, "{} contains '{}'", path.display(), pattern)
+            }
+            Evidence::Dependency { manifest, name } => {
+                write!(f, "dependency '{}' in {}", name, manifest.display())
+            }
+            Evidence::ManifestKey { manifest, key } => {
+                write!(f, "{} in {}", key, manifest.display())
+            }
+            Evidence::CargoTarget {
+                package,
+                target,
+                kind,
+            } => write!(
+                f,
+                "{} target '{}' of package '{}'",
+                kind.as_str(),
+                target,
+                package
+            ),
+            Evidence::Recognized { manifest, provider } => {
+                write!(
+                    f,
+                    "{} recognized by provider '{}'",
+                    manifest.display(),
+                    provider
+                )
             }
+            Evidence::Implied { types } => write!(f, "implied by {}", types.join(", ")),
         }
     }
+}
 
-    // Add language-specific types based on file extensions
-    if has_python_files {
-        detected_types.push("python".to_string());
+/// A detected project type together with what triggered it
+#[derive(Debug, Clone, Serialize)]
+pub struct Detection {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Highest confidence of any evidence for the type
+    pub confidence: Confidence,
+    pub evidence: Vec<Evidence>,
+    /// Number of weaker evidence items left out of `evidence`
+    pub omitted: usize,
+}
+
+/// Collects detections, merging repeated hits for the same type
+struct Detections<'a> {
+    root: &'a Path,
+    items: Vec<(String, Vec<(Confidence, Evidence)>)>,
+}
+
+impl Detections<'_> {
+    fn contains(&self, type_name: &str) -> bool {
+        self.items.iter().any(|(name, _)| name == type_name)
     }
-    if has_js_files {
-        detected_types.push("javascript".to_string());
+
+    fn add(&mut self, type_name: &str, confidence: Confidence, evidence: Evidence) {
+        let evidence = (confidence, self.relative(evidence));
+        match self.items.iter_mut().find(|(name, _)| name == type_name) {
+            Some((_, found)) => match found.iter_mut().find(|(_, e)| *e == evidence.1) {
+                Some(same) => same.0 = same.0.max(evidence.0),
+                None => found.push(evidence),
+            },
+            None => self.items.push((type_name.to_string(), vec![evidence])),
+        }
+    }
+
+    /// Sorts the types by name and keeps the strongest evidence of each,
+    /// listing what a type was implied by first
+    fn finish(self) -> Vec<Detection> {
+        let mut detections: Vec<Detection> = self
+            .items
+            .into_iter()
+            .map(|(type_name, mut found)| {
+                found.sort_by_key(|(confidence, evidence)| {
+                    (
+                        std::cmp::Reverse(*confidence),
+                        !matches!(evidence, Evidence::Implied { .. }),
+                    )
+                });
+                let omitted = found.len().saturating_sub(MAX_EVIDENCE);
+                found.truncate(MAX_EVIDENCE);
+                Detection {
+                    type_name,
+                    confidence: found[0].0,
+                    evidence: found.into_iter().map(|(_, evidence)| evidence).collect(),
+                    omitted,
+                }
+            })
+            .collect();
+        detections.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+        detections
+    }
+
+    fn relative(&self, evidence: Evidence) -> Evidence {
+        let strip = |path: PathBuf| match path.strip_prefix(self.root) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => path,
+        };
+        match evidence {
+            Evidence::File { path } => Evidence::File { path: strip(path) },
+            Evidence::Content { path, pattern } => Evidence::Content {
+                path: strip(path),
+                pattern,
+            },
+            Evidence::Dependency { manifest, name } => Evidence::Dependency {
+                manifest: strip(manifest),
+                name,
+            },
+            Evidence::ManifestKey { manifest, key } => Evidence::ManifestKey {
+                manifest: strip(manifest),
+                key,
+            },
+            Evidence::Recognized { manifest, provider } => Evidence::Recognized {
+                manifest: strip(manifest),
+                provider,
+            },
+            other => other,
+        }
     }
-    if has_ts_files {
-        detected_types.push("typescript".to_string());
+}
+
+pub fn detect_project_types(
+    project: &Project,
+    providers: &[Box<dyn ConfigProvider>],
+) -> Result<Vec<Detection>, Box<dyn std::error::Error>> {
+    let mut detected = Detections {
+        root: &project.root,
+        items: Vec::new(),
+    };
+
+    // Check each provider for file-based detection
+    for path in project.files() {
+        for provider in providers.iter() {
+            if provider.can_detect_from_file(path) {
+                detected.add(
+                    provider.name(),
+                    Confidence::Medium,
+                    Evidence::File { path: path.clone() },
+                );
+            }
+        }
     }
-    if has_cpp_files {
-        detected_types.push("cpp-gdb".to_string());
+
+    // Add language-specific types based on file extensions
+    let languages: [(&str, &[&str]); 4] = [
+        ("python", &["py"]),
+        ("javascript", &["js"]),
+        ("typescript", &["ts"]),
+        ("cpp-gdb", &["cpp", "cc", "cxx", "h", "hpp"]),
+    ];
+    for (type_name, exts) in languages {
+        if let Some(path) = exts
+            .iter()
+            .find_map(|ext| project.files_with_extension(ext).first())
+        {
+            detected.add(
+                type_name,
+                Confidence::Medium,
+                Evidence::File { path: path.clone() },
+            );
+        }
     }
 
     // Special handling for npm/node
-    if detected_files.contains_key("package.json") {
-        detected_types.push("node".to_string());
+    if let Some((manifest, content)) = project.manifest("package.json") {
+        detected.add(
+            "node",
+            Confidence::High,
+            Evidence::File {
+                path: manifest.to_path_buf(),
+            },
+        );
 
         // Check package.json for specific frameworks
-        if let Ok(content) = fs::read_to_string(detected_files.get("package.json").unwrap()) {
-            if let Ok(json) = serde_json::from_str::<Value>(&content) {
-                if let Some(deps) = json.get("dependencies") {
-                    if deps.get("react").is_some() {
-                        detected_types.push("react".to_string());
-                    }
-                    if deps.get("vue").is_some() {
-                        detected_types.push("vue".to_string());
-                    }
-                    if deps.get("express").is_some() {
-                        detected_types.push("express".to_string());
-                    }
+        if let Ok(json) = serde_json::from_str::<Value>(content)
+            && let Some(deps) = json.get("dependencies")
+        {
+            for framework in ["react", "vue", "express"] {
+                if deps.get(framework).is_some() {
+                    detected.add(
+                        framework,
+                        Confidence::High,
+                        Evidence::Dependency {
+                            manifest: manifest.to_path_buf(),
+                            name: framework.to_string(),
+                        },
+                    );
                 }
             }
         }
     }
 
     // Special handling for Python frameworks
-    if detected_files.contains_key("requirements.txt") {
-        if let Ok(content) = fs::read_to_string(detected_files.get("requirements.txt").unwrap()) {
-            if content.contains("flask") {
-                detected_types.push("flask".to_string());
-            }
-            if content.contains("fastapi") {
-                detected_types.push("fastapi".to_string());
-            }
-            if content.contains("django") {
-                detected_types.push("python-module:django".to_string());
+    let frameworks: Vec<(&str, &str)> = [
+        ("flask", "flask"),
+        ("fastapi", "fastapi"),
+        ("django", "django"),
+        ("pytest", "python-module:pytest"),
+    ]
+    .into_iter()
+    // Django configurations run manage.py, so the dependency alone is not enough
+    .filter(|(name, _)| *name != "django" || project.manifest("manage.py").is_some())
+    .collect();
+    // Requirements files can include others, which may lie outside the scan
+    let read = |path: &Path| {
+        project
+            .manifest_at(path)
+            .map(String::from)
+            .or_else(|| fs::read_to_string(path).ok())
+    };
+    for (path, content) in project.manifests() {
+        if !python::is_requirements_file(path) {
+            continue;
+        }
+        detected.add(
+            "python",
+            Confidence::High,
+            Evidence::File {
+                path: path.to_path_buf(),
+            },
+        );
+        for (manifest, name) in python::requirements_with_includes(path, content, read) {
+            if let Some((_, type_name)) = frameworks.iter().find(|(dep, _)| *dep == name) {
+                detected.add(
+                    type_name,
+                    Confidence::High,
+                    Evidence::Dependency { manifest, name },
+                );
             }
-            if content.contains("pytest") {
-                detected_types.push("python-module:pytest".to_string());
+        }
+    }
+    for file_name in python::MANIFESTS {
+        let Some((manifest, content)) = project.manifest(file_name) else {
+            continue;
+        };
+        for entry_point in python::entry_points(file_name, content) {
+            detected.add(
+                "python-scripts",
+                Confidence::High,
+                Evidence::ManifestKey {
+                    manifest: manifest.to_path_buf(),
+                    key: format!("script '{}'", entry_point.name),
+                },
+            );
+        }
+        let Some(dependencies) = python::dependencies(file_name, content) else {
+            continue;
+        };
+        detected.add(
+            "python",
+            Confidence::High,
+            Evidence::File {
+                path: manifest.to_path_buf(),
+            },
+        );
+        for &(name, type_name) in &frameworks {
+            if dependencies.iter().any(|d| d == name) {
+                detected.add(
+                    type_name,
+                    Confidence::High,
+                    Evidence::Dependency {
+                        manifest: manifest.to_path_buf(),
+                        name: name.to_string(),
+                    },
+                );
             }
         }
     }
 
-    // Special handling for Rust projects
-    if has_rust_files {
-        detected_types.push("rust".to_string());
-
-        // Check if this is a library project
-        if let Some(cargo_toml) = detected_files.get("Cargo.toml") {
-            if let Ok(content) = fs::read_to_string(cargo_toml) {
-                if content.contains("[lib]") || !content.contains("[[bin]]") {
-                    detected_types.push("rust-lib".to_string());
+    // Special handling for Rust projects, including every workspace member
+    let packages = project.cargo_packages();
+    let rust_file = project.files_with_extension("rs").first();
+    if rust_file.is_some() || !packages.is_empty() {
+        let target_types = [
+            ("rust", TargetKind::Bin),
+            ("rust-lib", TargetKind::Lib),
+            ("rust-test", TargetKind::Test),
+            // Examples and benches are only known from the Cargo targets
+            ("rust-example", TargetKind::Example),
+            ("rust-bench", TargetKind::Bench),
+        ];
+        for (type_name, kind) in target_types {
+            for package in packages {
+                for target in package.targets_of(kind) {
+                    detected.add(
+                        type_name,
+                        Confidence::High,
+                        Evidence::CargoTarget {
+                            package: package.name.clone(),
+                            target: target.name.clone(),
+                            kind,
+                        },
+                    );
                 }
             }
         }
 
-        // Check for test files
-        let mut has_tests = false;
-        for entry in WalkDir::new(".")
-            .max_depth(3)
-            .into_iter()
-            .filter_map(|e| e.ok())
+        if let Some(path) = rust_file {
+            detected.add(
+                "rust",
+                Confidence::Medium,
+                Evidence::File { path: path.clone() },
+            );
+        } else if !detected.contains("rust") {
+            // A package without binaries still gets the fallback config
+            detected.add(
+                "rust",
+                Confidence::Medium,
+                Evidence::File {
+                    path: project.root.join("Cargo.toml"),
+                },
+            );
+        }
+
+        // Without Cargo targets, guess a library from the manifest
+        if packages.is_empty()
+            && let Some((manifest, content)) = project.manifest("Cargo.toml")
         {
-            let path = entry.path();
-            if path.is_file() && path.extension().map_or(false, |ext| ext == "rs") {
-                if let Ok(content) = fs::read_to_string(path) {
-                    if content.contains("#[test]") || content.contains("mod test") {
-                        has_tests = true;
-                        break;
-                    }
-                }
+            if content.contains("[lib]") {
+                detected.add(
+                    "rust-lib",
+                    Confidence::High,
+                    Evidence::ManifestKey {
+                        manifest: manifest.to_path_buf(),
+                        key: "[lib]".to_string(),
+                    },
+                );
+            } else if !content.contains("[[bin]]") {
+                detected.add(
+                    "rust-lib",
+                    Confidence::Low,
+                    Evidence::ManifestKey {
+                        manifest: manifest.to_path_buf(),
+                        key: "no [[bin]]".to_string(),
+                    },
+                );
             }
         }
 
-        if has_tests {
-            detected_types.push("rust-test".to_string());
+        // Check for test modules
+        if let Some(path) = project.find_source("rs", |content| {
+            content.contains("#[test]") || content.contains("mod test")
+        }) {
+            detected.add(
+                "rust-test",
+                Confidence::Medium,
+                Evidence::Content {
+                    path: path.to_path_buf(),
+                    pattern: "#[test]".to_string(),
+                },
+            );
         }
 
         // If we have at least 2 of the 3 types, suggest the all-in-one config
-        let rust_types = [
-            "rust".to_string(),
-            "rust-lib".to_string(),
-            "rust-test".to_string(),
-        ];
-        let count = rust_types
-            .iter()
-            .filter(|t| detected_types.contains(t))
-            .count();
-        if count >= 2 {
-            detected_types.push("rust-all".to_string());
+        let rust_types: Vec<String> = ["rust", "rust-lib", "rust-test"]
+            .into_iter()
+            .filter(|t| detected.contains(t))
+            .map(String::from)
+            .collect();
+        if rust_types.len() >= 2 {
+            detected.add(
+                "rust-all",
+                Confidence::Medium,
+                Evidence::Implied { types: rust_types },
+            );
         }
     }
 
     // Perform content-based detection for special files
-    for (filename, path) in &detected_files {
-        if let Ok(content) = fs::read_to_string(path) {
-            for provider in providers.iter() {
-                if provider.can_detect_from_content(filename, &content) {
-                    // Skip if we already added the type through file detection
-                    if !detected_types.contains(&provider.name().to_string()) {
-                        detected_types.push(provider.name().to_string());
-                    }
-                }
+    for (path, content) in project.manifests() {
+        let filename = path.file_name().unwrap_or_default().to_string_lossy();
+        for provider in providers.iter() {
+            if provider.can_detect_from_content(&filename, content) {
+                detected.add(
+                    provider.name(),
+                    Confidence::Medium,
+                    Evidence::Recognized {
+                        manifest: path.to_path_buf(),
+                        provider: provider.name().to_string(),
+                    },
+                );
             }
         }
     }
 
-    // Remove duplicates
-    detected_types.sort();
-    detected_types.dedup();
-
-    Ok(detected_types)
+    Ok(detected.finish())
 }
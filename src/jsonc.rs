@@ -0,0 +1,445 @@
+use std::ops::Range;
+
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::ser::{PrettyFormatter, Serializer};
+
+/// UTF-8 byte order mark, which editors on Windows may put in front of JSON
+const BOM: &str = "\u{feff}";
+
+/// Replaces `//` and `/* */` comments and a leading byte order mark with
+/// spaces, keeping byte offsets and line breaks intact so that positions in
+/// the result map back to `text`
+fn strip_comments(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut i = 0;
+    if text.starts_with(BOM) {
+        out[..BOM.len()].fill(b' ');
+        i = BOM.len();
+    }
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = string_end(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = text[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |p| i + 2 + p + 2);
+                for byte in &mut out[i..end] {
+                    if *byte != b'\n' && *byte != b'\r' {
+                        *byte = b' ';
+                    }
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    // Only ASCII bytes outside strings were replaced, so this stays valid UTF-8
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Replaces commas that directly precede a closing `]` or `}` with spaces.
+/// Expects input that already went through [`strip_comments`].
+fn strip_trailing_commas(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = string_end(bytes, i),
+            b',' => {
+                let next = skip_whitespace(bytes, i + 1);
+                if matches!(bytes.get(next), Some(b']') | Some(b'}')) {
+                    out[i] = b' ';
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Parses a JSONC document (JSON with comments and trailing commas). A
+/// document without any value, such as an empty file, parses as `null`.
+pub fn parse(text: &str) -> Result<Value, serde_json::Error> {
+    let stripped = strip_trailing_commas(&strip_comments(text));
+    if stripped.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(&stripped)
+}
+
+/// Returns the index just past the string literal starting at `start`
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+/// Returns the index just past the JSON value starting at `start`
+fn value_end(bytes: &[u8], start: usize) -> usize {
+    match bytes.get(start) {
+        Some(b'"') => string_end(bytes, start),
+        Some(b'{') | Some(b'[') => {
+            let mut depth = 0;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = string_end(bytes, i);
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return i + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        _ => {
+            let mut i = start;
+            while i < bytes.len() && !matches!(bytes[i], b',' | b']' | b'}') {
+                if bytes[i].is_ascii_whitespace() {
+                    break;
+                }
+                i += 1;
+            }
+            i
+        }
+    }
+}
+
+/// Location of an array-valued top-level property inside a JSONC document
+#[derive(Debug)]
+struct ArraySpan {
+    /// Offset of the property key's opening quote
+    key: usize,
+    /// Offset of the closing `]`
+    close: usize,
+    /// Byte ranges of each element value
+    elements: Vec<Range<usize>>,
+    /// Whether the last element is followed by a comma
+    trailing_comma: bool,
+}
+
+/// Finds the array stored under `key` in the top-level object of `stripped`,
+/// which must have had its comments removed by [`strip_comments`]
+fn find_top_level_array(stripped: &str, key: &str) -> Option<ArraySpan> {
+    let bytes = stripped.as_bytes();
+    let mut i = skip_whitespace(bytes, 0);
+    if bytes.get(i) != Some(&b'{') {
+        return None;
+    }
+    i += 1;
+    loop {
+        i = skip_whitespace(bytes, i);
+        match bytes.get(i) {
+            Some(b'"') => {}
+            Some(b',') => {
+                i += 1;
+                continue;
+            }
+            _ => return None,
+        }
+        let key_start = i;
+        let key_end = string_end(bytes, i);
+        let name = stripped.get(key_start + 1..key_end.saturating_sub(1))?;
+        i = skip_whitespace(bytes, key_end);
+        if bytes.get(i) != Some(&b':') {
+            return None;
+        }
+        i = skip_whitespace(bytes, i + 1);
+        if name == key && bytes.get(i) == Some(&b'[') {
+            let mut elements = Vec::new();
+            let mut trailing_comma = false;
+            i += 1;
+            loop {
+                i = skip_whitespace(bytes, i);
+                match bytes.get(i) {
+                    Some(b']') => {
+                        return Some(ArraySpan {
+                            key: key_start,
+                            close: i,
+                            elements,
+                            trailing_comma,
+                        });
+                    }
+                    Some(b',') => {
+                        trailing_comma = true;
+                        i += 1;
+                    }
+                    Some(_) => {
+                        let end = value_end(bytes, i);
+                        elements.push(i..end);
+                        trailing_comma = false;
+                        i = end;
+                    }
+                    None => return None,
+                }
+            }
+        }
+        i = value_end(bytes, i);
+    }
+}
+
+/// The line ending a document uses, judged by its first line break
+pub fn line_ending(text: &str) -> &'static str {
+    match text.find('\n') {
+        Some(p) if text[..p].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
+/// Returns the leading whitespace of the line containing `offset`
+fn line_indent(text: &str, offset: usize) -> &str {
+    let line_start = text[..offset].rfind('\n').map_or(0, |p| p + 1);
+    let line = &text[line_start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Serializes `value` with the given indentation unit, ending every line but
+/// the last with `newline` and prefixing it with `indent`
+fn to_indented_string(value: &Value, unit: &str, indent: &str, newline: &str) -> String {
+    let mut buf = Vec::new();
+    let formatter = PrettyFormatter::with_indent(unit.as_bytes());
+    let mut serializer = Serializer::with_formatter(&mut buf, formatter);
+    value
+        .serialize(&mut serializer)
+        .expect("serializing a JSON value cannot fail");
+    String::from_utf8(buf)
+        .unwrap_or_default()
+        .replace('\n', &format!("{}{}", newline, indent))
+}
+
+/// Rewrites the array stored under the top-level `key` of a JSONC document,
+/// replacing the elements at the given indices and appending new ones.
+///
+/// Everything outside the touched elements, including comments, key order and
+/// formatting, is kept as-is. Returns `None` if the document has no such array.
+pub fn rewrite_array(
+    text: &str,
+    key: &str,
+    replacements: &[(usize, Value)],
+    appended: &[Value],
+) -> Option<String> {
+    let stripped = strip_comments(text);
+    let span = find_top_level_array(&stripped, key)?;
+    let newline = line_ending(text);
+
+    let unit = match line_indent(text, span.key) {
+        "" => "  ",
+        unit => unit,
+    };
+    let indent = match span.elements.first() {
+        Some(first)
+            if text[..first.start]
+                .trim_end_matches([' ', '\t'])
+                .ends_with('\n') =>
+        {
+            line_indent(text, first.start).to_string()
+        }
+        _ => format!("{}{}", line_indent(text, span.key), unit),
+    };
+
+    // Collect edits as (range, replacement) and apply them back to front
+    let mut edits: Vec<(Range<usize>, String)> = replacements
+        .iter()
+        .filter_map(|(index, value)| {
+            let range = span.elements.get(*index)?.clone();
+            Some((range, to_indented_string(value, unit, &indent, newline)))
+        })
+        .collect();
+
+    if !appended.is_empty() {
+        if let Some(last) = span.elements.last()
+            && !span.trailing_comma
+        {
+            edits.push((last.end..last.end, ",".to_string()));
+        }
+
+        let separator = if span.trailing_comma { "," } else { "" };
+        let items: Vec<String> = appended
+            .iter()
+            .map(|value| {
+                let value = to_indented_string(value, unit, &indent, newline);
+                format!("{}{}", indent, value)
+            })
+            .collect();
+        let items = items.join(&format!(",{}", newline));
+
+        let close_line_start = text[..span.close].rfind('\n').map(|p| p + 1);
+        let insertion = match close_line_start {
+            Some(start) if text[start..span.close].trim().is_empty() => {
+                (start..start, format!("{}{}{}", items, separator, newline))
+            }
+            _ => (
+                span.close..span.close,
+                format!(
+                    "{}{}{}{}{}",
+                    newline,
+                    items,
+                    separator,
+                    newline,
+                    line_indent(text, span.key)
+                ),
+            ),
+        };
+        edits.push(insertion);
+    }
+
+    // Edits at the same offset are applied last to first, so that the comma
+    // after a last element ending at `]` stays in front of the new ones
+    edits.reverse();
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut result = text.to_string();
+    for (range, replacement) in edits {
+        result.replace_range(range, &replacement);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_keeps_comment_markers_inside_strings() {
+        let text = r#"{
+            // a comment
+            "url": "http://localhost:3000", /* block */
+            "glob": "src/**/*.js"
+        }"#;
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({ "url": "http://localhost:3000", "glob": "src/**/*.js" })
+        );
+    }
+
+    #[test]
+    fn parse_handles_escaped_quotes() {
+        let text = r#"{ "args": ["say \"hi\" // not a comment", "\\"], }"#;
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({ "args": ["say \"hi\" // not a comment", "\\"] })
+        );
+    }
+
+    #[test]
+    fn parse_accepts_trailing_commas() {
+        let text = "{ \"a\": [1, 2, ], \"b\": { \"c\": \"],\" , }, }";
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({ "a": [1, 2], "b": { "c": "]," } })
+        );
+    }
+
+    #[test]
+    fn parse_skips_a_byte_order_mark() {
+        assert_eq!(parse("\u{feff}{ \"a\": 1 }").unwrap(), json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn parse_reads_blank_documents_as_null() {
+        assert_eq!(parse("").unwrap(), Value::Null);
+        assert_eq!(parse(" \r\n // nothing\n").unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn rewrite_keeps_a_byte_order_mark() {
+        let text = "\u{feff}{ \"configurations\": [] }";
+        let result = rewrite_array(text, "configurations", &[], &[json!(1)]).unwrap();
+        assert!(result.starts_with(BOM));
+        assert_eq!(parse(&result).unwrap(), json!({ "configurations": [1] }));
+    }
+
+    #[test]
+    fn rewrite_appends_to_empty_array() {
+        let text = "{\n  \"version\": \"0.2.0\",\n  \"configurations\": []\n}\n";
+        let result = rewrite_array(text, "configurations", &[], &[json!({ "name": "A" })]).unwrap();
+        assert_eq!(
+            result,
+            "{\n  \"version\": \"0.2.0\",\n  \"configurations\": [\n    {\n      \"name\": \"A\"\n    }\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn rewrite_appends_to_single_line_array() {
+        let text = "{\n  \"configurations\": [{ \"name\": \"A\" }]\n}";
+        let result = rewrite_array(text, "configurations", &[], &[json!({ "name": "B" })]).unwrap();
+        assert_eq!(
+            parse(&result).unwrap(),
+            json!({ "configurations": [{ "name": "A" }, { "name": "B" }] })
+        );
+        assert!(result.starts_with("{\n  \"configurations\": [{ \"name\": \"A\" },"));
+    }
+
+    #[test]
+    fn rewrite_replaces_and_appends_keeping_comments() {
+        let text = "{\n  \"configurations\": [\n    // first\n    { \"name\": \"A\" },\n    { \"name\": \"B\" }, // trailing\n  ]\n}";
+        let result = rewrite_array(
+            text,
+            "configurations",
+            &[(1, json!({ "name": "B", "x": 1 }))],
+            &[json!({ "name": "C" })],
+        )
+        .unwrap();
+        assert!(result.contains("// first"));
+        assert!(result.contains("// trailing"));
+        assert_eq!(
+            parse(&result).unwrap(),
+            json!({ "configurations": [
+                { "name": "A" },
+                { "name": "B", "x": 1 },
+                { "name": "C" }
+            ] })
+        );
+    }
+
+    #[test]
+    fn rewrite_requires_the_array() {
+        assert!(rewrite_array("{ \"version\": \"0.2.0\" }", "configurations", &[], &[]).is_none());
+        assert!(rewrite_array("[]", "configurations", &[], &[]).is_none());
+    }
+
+    #[test]
+    fn rewrite_keeps_crlf_line_endings() {
+        let text = "{\r\n  \"configurations\": [\r\n    { \"name\": \"A\" }\r\n  ]\r\n}\r\n";
+        let result = rewrite_array(
+            text,
+            "configurations",
+            &[],
+            &[json!({ "name": "B", "x": [1] })],
+        )
+        .unwrap();
+        assert_eq!(result.matches('\n').count(), result.matches("\r\n").count());
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value, json};
+
+/// Kind of debug session a configuration starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Request {
+    Launch,
+    Attach,
+}
+
+/// A single entry of the `configurations` array in launch.json.
+///
+/// Holds the properties shared by all debuggers; everything specific to a
+/// debug adapter lives in [`Debugger`], which also determines the `type`.
+#[derive(Debug, Clone)]
+pub struct LaunchConfig {
+    pub name: String,
+    pub request: Request,
+    pub program: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub cwd: Option<String>,
+    pub pre_launch_task: Option<String>,
+    pub debugger: Debugger,
+    /// Additional properties written as-is, for keys the model does not cover
+    pub extra: Map<String, Value>,
+}
+
+impl LaunchConfig {
+    /// Creates a launch request for the given debugger with no optional
+    /// properties set
+    pub fn new(name: impl Into<String>, debugger: Debugger) -> Self {
+        LaunchConfig {
+            name: name.into(),
+            request: Request::Launch,
+            program: None,
+            args: None,
+            cwd: None,
+            pre_launch_task: None,
+            debugger,
+            extra: Map::new(),
+        }
+    }
+
+    /// Adds environment variables in the debugger's format, replacing ones
+    /// with the same name. Returns false if the debugger takes none.
+    pub fn extend_env(&mut self, vars: &BTreeMap<String, String>) -> bool {
+        let env = match &mut self.debugger {
+            Debugger::Debugpy(options) | Debugger::Python(options) => &mut options.env,
+            Debugger::Node(options) => &mut options.env,
+            Debugger::Lldb(options) => &mut options.env,
+            Debugger::Cppdbg(options) => {
+                let environment = options.environment.get_or_insert_with(Vec::new);
+                for (name, value) in vars {
+                    environment.retain(|var| &var.name != name);
+                    environment.push(EnvironmentVariable {
+                        name: name.clone(),
+                        value: value.clone(),
+                    });
+                }
+                return true;
+            }
+            // Configurations read from launch.json keep their settings in
+            // `extra`; only adapters known to take an `env` object get one
+            Debugger::Other(type_name) => {
+                if !matches!(type_name.as_str(), "debugpy" | "python" | "node" | "lldb") {
+                    return false;
+                }
+                let Value::Object(env) = self.extra.entry("env").or_insert_with(|| json!({}))
+                else {
+                    return false;
+                };
+                env.extend(vars.iter().map(|(k, v)| (k.clone(), json!(v))));
+                return true;
+            }
+            Debugger::Chrome(_) => return false,
+        };
+        env.get_or_insert_with(BTreeMap::new)
+            .extend(vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+        true
+    }
+
+    /// Builds a configuration from its launch.json representation, keeping
+    /// every property as given. Adapter settings are not interpreted: the
+    /// debugger is [`Debugger::Other`] and its settings stay in `extra`.
+    pub fn from_value(value: Value) -> Result<Self, String> {
+        let Value::Object(mut map) = value else {
+            return Err("configuration is not a JSON object".to_string());
+        };
+        let name = take_string(&mut map, "name")?.ok_or("missing property 'name'")?;
+        let type_name = take_string(&mut map, "type")?.ok_or("missing property 'type'")?;
+        let request = match take_string(&mut map, "request")?.as_deref() {
+            None | Some("launch") => Request::Launch,
+            Some("attach") => Request::Attach,
+            Some(request) => return Err(format!("unsupported request '{}'", request)),
+        };
+        let program = take_string(&mut map, "program")?;
+        let cwd = take_string(&mut map, "cwd")?;
+        let pre_launch_task = take_string(&mut map, "preLaunchTask")?;
+        // Some adapters also accept `args` as a single string, which is kept
+        // in `extra`
+        let args = match map
+            .get("args")
+            .map(|args| serde_json::from_value(args.clone()))
+        {
+            Some(Ok(args)) => {
+                map.remove("args");
+                Some(args)
+            }
+            _ => None,
+        };
+
+        Ok(LaunchConfig {
+            name,
+            request,
+            program,
+            args,
+            cwd,
+            pre_launch_task,
+            debugger: Debugger::Other(type_name),
+            extra: map,
+        })
+    }
+
+    /// Converts the configuration into its launch.json representation
+    pub fn to_value(&self) -> Value {
+        let mut map = Map::new();
+        map.insert("name".to_string(), json!(self.name));
+        map.insert("type".to_string(), json!(self.debugger.type_name()));
+        map.insert("request".to_string(), json!(self.request));
+        if let Some(program) = &self.program {
+            map.insert("program".to_string(), json!(program));
+        }
+        if let Value::Object(options) = self.debugger.options_value() {
+            map.extend(options);
+        }
+        if let Some(args) = &self.args {
+            map.insert("args".to_string(), json!(args));
+        }
+        if let Some(cwd) = &self.cwd {
+            map.insert("cwd".to_string(), json!(cwd));
+        }
+        if let Some(task) = &self.pre_launch_task {
+            map.insert("preLaunchTask".to_string(), json!(task));
+        }
+        map.extend(self.extra.clone());
+        Value::Object(map)
+    }
+}
+
+/// Removes a string property from `map`
+fn take_string(map: &mut Map<String, Value>, key: &str) -> Result<Option<String>, String> {
+    match map.remove(key) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => Err(format!("property '{}' must be a string", key)),
+    }
+}
+
+impl Serialize for LaunchConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+/// Debug adapter of a configuration together with its adapter-specific settings
+#[derive(Debug, Clone)]
+pub enum Debugger {
+    /// Python via the debugpy extension
+    Debugpy(PythonOptions),
+    /// Python via the legacy `python` debugger type
+    Python(PythonOptions),
+    /// Node.js
+    Node(NodeOptions),
+    /// Chrome browser
+    Chrome(ChromeOptions),
+    /// C/C++ extension with GDB or LLDB
+    Cppdbg(CppdbgOptions),
+    /// CodeLLDB
+    Lldb(LldbOptions),
+    /// Any other debugger type, or a configuration read from launch.json, with
+    /// all its settings in [`LaunchConfig::extra`]
+    Other(String),
+}
+
+impl Debugger {
+    /// The `type` property for launch.json
+    pub fn type_name(&self) -> &str {
+        match self {
+            Debugger::Debugpy(_) => "debugpy",
+            Debugger::Python(_) => "python",
+            Debugger::Node(_) => "node",
+            Debugger::Chrome(_) => "chrome",
+            Debugger::Cppdbg(_) => "cppdbg",
+            Debugger::Lldb(_) => "lldb",
+            Debugger::Other(type_name) => type_name,
+        }
+    }
+
+    fn options_value(&self) -> Value {
+        let value = match self {
+            Debugger::Debugpy(options) | Debugger::Python(options) => serde_json::to_value(options),
+            Debugger::Node(options) => serde_json::to_value(options),
+            Debugger::Chrome(options) => serde_json::to_value(options),
+            Debugger::Cppdbg(options) => serde_json::to_value(options),
+            Debugger::Lldb(options) => serde_json::to_value(options),
+            Debugger::Other(_) => return Value::Null,
+        };
+        value.unwrap_or_default()
+    }
+}
+
+/// Settings for `debugpy` and `python` configurations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    /// Source to run instead of a program or module
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Interpreter to debug with instead of the one selected in VS Code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub python: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub console: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jinja: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub django: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub just_my_code: Option<bool>,
+}
+
+/// Settings for `node` configurations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub console: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_files: Option<Vec<String>>,
+}
+
+/// Settings for `chrome` configurations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChromeOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_root: Option<String>,
+}
+
+/// Settings for `cppdbg` configurations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CppdbgOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_at_entry: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Vec<EnvironmentVariable>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_console: Option<bool>,
+    #[serde(rename = "MIMode", skip_serializing_if = "Option::is_none")]
+    pub mi_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup_commands: Option<Vec<SetupCommand>>,
+}
+
+/// Entry of the cppdbg `environment` array
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentVariable {
+    pub name: String,
+    pub value: String,
+}
+
+/// Debugger command run by cppdbg before the program starts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupCommand {
+    pub description: String,
+    pub text: String,
+    pub ignore_failures: bool,
+}
+
+/// Settings for CodeLLDB `lldb` configurations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LldbOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cargo: Option<CargoOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, String>>,
+}
+
+/// CodeLLDB's cargo integration: build with `args`, then launch the
+/// executable matching `filter`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CargoOptions {
+    pub args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<CargoFilter>,
+}
+
+/// Selects the executable produced by a cargo build
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CargoFilter {
+    pub name: String,
+    pub kind: String,
+}
@@ -1,37 +1,79 @@
-use clap::Parser;
-use detect::detect_project_types;
+use clap::{Parser, ValueEnum};
+use config::Config;
+use detect::{Detection, detect_project_types};
+use launch::LaunchConfig;
+use project::{Project, ScanOptions};
 use providers::{
-    CppGdbConfigProvider, CppLldbConfigProvider, FastApiConfigProvider, FlaskConfigProvider,
-    JavaScriptConfigProvider, NodeConfigProvider, PythonConfigProvider, PythonModuleConfigProvider,
-    RustAllConfigProvider, RustConfigProvider, RustLibConfigProvider, RustTestConfigProvider,
-    TypeScriptConfigProvider,
+    CppGdbConfigProvider, CppLldbConfigProvider, DjangoConfigProvider, FastApiConfigProvider,
+    FlaskConfigProvider, JavaScriptConfigProvider, NodeConfigProvider, PythonConfigProvider,
+    PythonModuleConfigProvider, PythonScriptsConfigProvider, RustAllConfigProvider,
+    RustBenchConfigProvider, RustConfigProvider, RustExampleConfigProvider, RustLibConfigProvider,
+    RustTestConfigProvider, TypeScriptConfigProvider,
 };
 use serde_json::{Value, json};
-use std::collections::HashMap;
+use similar::TextDiff;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use types::ConfigProvider;
+use std::process::ExitCode;
+use types::{ConfigProvider, Params};
 
+mod cargo;
+mod config;
 mod detect;
+mod jsonc;
+mod launch;
+mod merge;
+mod project;
 mod providers;
+mod python;
+mod tasks;
+mod templates;
 mod types;
+mod validate;
 
 #[derive(Parser)]
 #[command(
     name = "launch-json-generator",
     version = "1.0",
     author = "Your Name",
-    about = "Generates VS Code launch.json configurations"
+    about = "Generates VS Code launch.json configurations",
+    after_help = "Exit status: 0 on success, 1 on errors, 2 on invalid arguments, 3 when no \
+                  configurations were detected or specified, 4 when --check finds launch.json \
+                  out of date"
 )]
 struct Cli {
-    /// Output file path (default: .vscode/launch.json)
+    /// Project directory to scan (default: current directory)
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    root: PathBuf,
+
+    /// How many directory levels below the root detection scans
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    max_depth: usize,
+
+    /// Leave paths matching this glob out of detection (can be specified
+    /// multiple times). Paths in .gitignore/.ignore are always left out
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Project configuration file with defaults and overrides
+    /// (default: <ROOT>/.vscode-launch-gen.toml, if present)
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Output file path (default: <ROOT>/.vscode/launch.json)
     #[arg(short, long, value_name = "FILE")]
     output: Option<PathBuf>,
 
-    /// Add configurations for specific types (can be specified multiple times)
-    /// Available types: python, python-module:<name>, flask, fastapi, javascript,
-    /// node, typescript, rust, cpp-gdb, cpp-lldb
+    /// Add configurations for specific types (can be specified multiple times).
+    /// Parameters follow a colon as key=value pairs separated by commas, e.g.
+    /// python-module:module=pytest,args=-x tests/
+    /// Available types: python, python-module, python-scripts, django, flask,
+    /// fastapi, javascript, node, typescript, rust, rust-lib, rust-test,
+    /// rust-example, rust-bench, rust-all, cpp-gdb, cpp-lldb, and the names of
+    /// template providers in <ROOT>/.vscode-launch-gen/providers or
+    /// vscode-launch-gen/providers in $XDG_CONFIG_HOME (default: ~/.config)
     #[arg(short, long, value_name = "TYPE")]
     r#type: Vec<String>,
 
@@ -39,18 +81,93 @@ struct Cli {
     #[arg(long)]
     detect: bool,
 
+    /// Also set Python interpreters found outside the project: Poetry, conda
+    /// and pyenv environments. Their paths are specific to this machine.
+    /// Can also be set with `external_python = true` in the config file
+    #[arg(long)]
+    external_python: bool,
+
     /// Print detected project types without generating files
     #[arg(long)]
     dry_run: bool,
+
+    /// Print the evidence and confidence behind each detected type. Acts like
+    /// --dry-run unless --detect is also given
+    #[arg(long)]
+    explain: bool,
+
+    /// Merge into an existing launch.json instead of overwriting it.
+    /// User-authored configurations are kept; previously generated ones are
+    /// updated in place
+    #[arg(long)]
+    merge: bool,
+
+    /// Print the resulting launch.json instead of writing it
+    #[arg(long, conflicts_with_all = ["diff", "format"])]
+    stdout: bool,
+
+    /// Show a unified diff of the changes to launch.json and tasks.json
+    /// instead of writing them
+    #[arg(long, conflicts_with = "format")]
+    diff: bool,
+
+    /// Compare the generated configurations with the existing launch.json
+    /// without writing anything, and fail if it is out of date.
+    /// User-authored configurations and the machine-specific `python`
+    /// interpreter are ignored
+    #[arg(long, conflicts_with_all = ["stdout", "diff"])]
+    check: bool,
+
+    /// How results are printed on stdout
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Human-readable messages
+    Text,
+    /// A single JSON object with the detected types, the generated
+    /// configurations and the paths written
+    Json,
+}
+
+/// Exit status when nothing was detected or specified. Clap exits with 2 on
+/// invalid arguments
+const EXIT_NOTHING_DETECTED: u8 = 3;
+/// Exit status when --check finds generated configurations out of date
+const EXIT_STALE: u8 = 4;
+
+fn main() -> ExitCode {
     let args = Cli::parse();
+    match run(&args) {
+        Ok(code) => code,
+        Err(err) => {
+            if args.format == Format::Json {
+                println!("{}", json!({ "error": err.to_string() }));
+            } else {
+                eprintln!("Error: {}", err);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
 
+fn run(args: &Cli) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let json_output = args.format == Format::Json;
+    // Previews and checks of the generated files keep stdout to themselves
+    let preview = args.stdout || args.diff || args.check;
+    if !args.root.is_dir() {
+        return Err(format!("Project root {} is not a directory", args.root.display()).into());
+    }
+    let config = Config::load(&args.root, args.config.as_deref())?;
+    let detect = args.detect || config.detect;
     // Register all available config providers
-    let providers: Vec<Box<dyn ConfigProvider>> = vec![
+    let mut providers: Vec<Box<dyn ConfigProvider>> = vec![
         Box::new(PythonConfigProvider),
         Box::new(PythonModuleConfigProvider),
+        Box::new(PythonScriptsConfigProvider),
+        Box::new(DjangoConfigProvider),
         Box::new(FlaskConfigProvider),
         Box::new(FastApiConfigProvider),
         Box::new(JavaScriptConfigProvider),
@@ -59,47 +176,126 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Box::new(RustConfigProvider),
         Box::new(RustLibConfigProvider),
         Box::new(RustTestConfigProvider),
+        Box::new(RustExampleConfigProvider),
+        Box::new(RustBenchConfigProvider),
         Box::new(RustAllConfigProvider),
         Box::new(CppGdbConfigProvider),
         Box::new(CppLldbConfigProvider),
     ];
 
+    // Template providers from the project replace user-level ones of the same
+    // name, but never built-in providers
+    let builtin_count = providers.len();
+    for template in templates::load_providers(&args.root) {
+        match providers.iter().position(|p| p.name() == template.name()) {
+            Some(index) if index < builtin_count => eprintln!(
+                "Warning: Ignoring template provider '{}', which is a built-in type",
+                template.name()
+            ),
+            Some(index) => providers[index] = Box::new(template),
+            None => providers.push(Box::new(template)),
+        }
+    }
+
+    let project = Project::scan(
+        &args.root,
+        &ScanOptions {
+            max_depth: args.max_depth,
+            exclude: args.exclude.clone(),
+            manifests: providers.iter().flat_map(|p| p.manifest_files()).collect(),
+            external_python: args.external_python || config.external_python,
+        },
+    )?;
+
     // Create a map for quick lookup by name
     let provider_map: HashMap<&str, &Box<dyn ConfigProvider>> =
         providers.iter().map(|p| (p.name(), p)).collect();
 
-    let mut configs: Vec<Value> = Vec::new();
+    let mut configs: Vec<LaunchConfig> = Vec::new();
 
     // If detect flag is set, detect project types
-    let mut detected_types = Vec::new();
-    if args.detect || args.dry_run {
-        detected_types = detect_project_types(&providers)?;
+    let mut detections = None;
+    if detect || args.dry_run || args.explain {
+        let mut detected = detect_project_types(&project, &providers)?;
+        detected.retain(|d| !config.is_disabled(&d.type_name));
 
         // Print detected project types
-        println!("Detected project types:");
-        if detected_types.is_empty() {
-            println!("  No specific project types detected");
-        } else {
-            for project_type in &detected_types {
-                println!("  - {}", project_type);
-            }
+        let generating = detect && !args.dry_run;
+        if !(json_output || generating && preview) {
+            print_detections(&detected, args.explain);
         }
 
         // If dry run, exit after printing detected types
-        if args.dry_run {
-            return Ok(());
+        if args.dry_run || !detect {
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "root": project.root,
+                        "detected": detected,
+                    }))?
+                );
+            }
+            return Ok(if detected.is_empty() {
+                ExitCode::from(EXIT_NOTHING_DETECTED)
+            } else {
+                ExitCode::SUCCESS
+            });
         }
+        detections = Some(detected);
     }
 
+    // Types from the command line replace config file types for the same provider
+    let provider_of = |type_arg: &str| type_arg.split(':').next().unwrap_or_default().to_string();
+    let mut requested = args.r#type.clone();
+    requested.extend(
+        config
+            .types
+            .iter()
+            .filter(|t| !args.r#type.iter().any(|a| provider_of(a) == provider_of(t)))
+            .cloned(),
+    );
+
+    // Parameters from the command line or detection, completed by the ones
+    // in the config file
+    let params_for = |provider: &dyn ConfigProvider,
+                      text: Option<&str>|
+     -> Result<Params, Box<dyn std::error::Error>> {
+        let keys = provider.param_keys();
+        let parse = |text: &str| {
+            Params::parse(text, &keys).map_err(|err| {
+                format!("Invalid parameters for type '{}': {}", provider.name(), err)
+            })
+        };
+        let given = text.map(parse).transpose()?.unwrap_or_default();
+        let defaults = config
+            .params
+            .get(provider.name())
+            .map(|text| parse(text))
+            .transpose()?
+            .unwrap_or_default();
+        Ok(given.or(defaults))
+    };
+
     // Process manually specified types
-    for type_arg in &args.r#type {
-        // Split by colon to handle parameterized types (e.g., python-module:django)
-        let parts: Vec<&str> = type_arg.splitn(2, ':').collect();
-        let type_name = parts[0];
-        let param = parts.get(1).copied();
+    for type_arg in &requested {
+        // Split by colon to handle parameterized types (e.g., python-module:module=pytest)
+        let (type_name, param) = match type_arg.split_once(':') {
+            Some((type_name, param)) => (type_name, Some(param)),
+            None => (type_arg.as_str(), None),
+        };
 
         if let Some(provider) = provider_map.get(type_name) {
-            configs.push(provider.get_config(param));
+            let params = params_for(provider.as_ref(), param)?;
+            let provided = provider.get_configs(&project, &params);
+            if provided.is_empty() {
+                eprintln!(
+                    "Warning: Type '{}' produced no configurations: {}",
+                    type_name,
+                    provider.no_configs_reason(&project)
+                );
+            }
+            configs.extend(provided);
         } else {
             eprintln!("Warning: Unknown configuration type: {}", type_name);
             eprintln!(
@@ -114,77 +310,303 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Add configurations for detected types if detect flag is set
-    if args.detect {
-        for type_name in detected_types {
-            // Skip if we already added this type manually
-            if args.r#type.iter().any(|t| t.starts_with(&type_name)) {
+    if let Some(detected) = &detections {
+        for detected_type in detected.iter().map(|d| &d.type_name) {
+            // Skip if we already added this provider manually
+            if requested
+                .iter()
+                .any(|t| provider_of(t) == provider_of(detected_type))
+            {
                 continue;
             }
 
-            // Special handling for python-module which might have parameters
-            if type_name.starts_with("python-module:") {
-                let parts: Vec<&str> = type_name.splitn(2, ':').collect();
-                if let Some(provider) = provider_map.get("python-module") {
-                    configs.push(provider.get_config(parts.get(1).copied()));
-                }
-            } else if let Some(provider) = provider_map.get(type_name.as_str()) {
-                configs.push(provider.get_config(None));
+            // Detected types can carry parameters too (e.g. python-module:pytest)
+            let (type_name, param) = match detected_type.split_once(':') {
+                Some((type_name, param)) => (type_name, Some(param)),
+                None => (detected_type.as_str(), None),
+            };
+            if let Some(provider) = provider_map.get(type_name) {
+                let params = params_for(provider.as_ref(), param)?;
+                configs.extend(provider.get_configs(&project, &params));
             }
         }
     }
 
     // If no configurations were specified through flags or detection, exit early
     if configs.is_empty() {
+        if json_output {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json!({
+                    "root": project.root,
+                    "detected": detections,
+                    "configurations": [],
+                }))?
+            );
+        } else if !requested.is_empty() {
+            println!("No configurations were generated for the requested types.");
+        } else {
+            println!(
+                "No configurations specified. Use --detect or specify configurations with --type."
+            );
+            println!(
+                "Available types: {}",
+                providers
+                    .iter()
+                    .map(|p| p.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        return Ok(ExitCode::from(EXIT_NOTHING_DETECTED));
+    }
+
+    // Providers can overlap (e.g. rust-all and rust), so keep the first
+    // configuration of each name
+    let mut seen_names = HashSet::new();
+    configs.retain(|config| seen_names.insert(config.name.clone()));
+
+    for launch_config in &mut configs {
+        config.apply_overrides(launch_config);
+    }
+    for name in config.unused_overrides(&configs) {
+        eprintln!("Warning: Override for unknown configuration '{}'", name);
+    }
+
+    let mut configs: Vec<Value> = configs.iter().map(LaunchConfig::to_value).collect();
+    for config in &mut configs {
+        merge::mark_generated(config);
+    }
+
+    // Refuse to write configurations the debug adapters would reject
+    let errors: Vec<String> = configs.iter().flat_map(validate::validate_config).collect();
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("Error: {}", error);
+        }
+        return Err("generated configurations failed validation".into());
+    }
+
+    let output_path = match (&args.output, &config.output) {
+        (Some(path), _) => path.clone(),
+        (None, Some(path)) => project.root.join(path),
+        (None, None) => project.root.join(".vscode").join("launch.json"),
+    };
+    if args.check {
+        return check_launch_json(&configs, &output_path, json_output);
+    }
+    let launch = render_launch_json(&configs, &output_path, args.merge)?;
+
+    // Make sure every referenced preLaunchTask exists next to launch.json
+    let tasks_path = output_path.with_file_name("tasks.json");
+    let tasks = render_tasks_json(&configs, &tasks_path)?;
+
+    // Preview instead of writing
+    if args.stdout {
+        println!("{}", launch);
+        return Ok(ExitCode::SUCCESS);
+    }
+    if args.diff {
+        print_diff(&output_path, &launch)?;
+        if let Some(tasks) = &tasks {
+            print_diff(&tasks_path, tasks)?;
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    write_file(&output_path, &launch)?;
+    if let Some(tasks) = &tasks {
+        write_file(&tasks_path, tasks)?;
+    }
+    let tasks_updated = tasks.is_some();
+
+    if json_output {
         println!(
-            "No configurations specified. Use --detect or specify configurations with --type."
-        );
-        println!(
-            "Available types: {}",
-            providers
-                .iter()
-                .map(|p| p.name())
-                .collect::<Vec<_>>()
-                .join(", ")
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "root": project.root,
+                "detected": detections,
+                "configurations": configs,
+                "output": output_path,
+                "tasks": tasks_updated.then_some(&tasks_path),
+            }))?
         );
-        return Ok(());
+    } else {
+        println!("Created launch.json at {}", output_path.display());
+        if tasks_updated {
+            println!("Updated tasks.json at {}", tasks_path.display());
+        }
     }
 
-    // Create launch.json file
-    let output_path = match args.output {
-        Some(path) => path,
-        None => {
-            let vscode_dir = Path::new(".vscode");
-            if !vscode_dir.exists() {
-                fs::create_dir(vscode_dir)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Prints the detected types, with their confidence and evidence if `explain`
+fn print_detections(detections: &[Detection], explain: bool) {
+    println!("Detected project types:");
+    if detections.is_empty() {
+        println!("  No specific project types detected");
+    }
+    for detection in detections {
+        if explain {
+            println!(
+                "  - {} ({} confidence)",
+                detection.type_name, detection.confidence
+            );
+            for evidence in &detection.evidence {
+                println!("      {}", evidence);
+            }
+            if detection.omitted > 0 {
+                println!("      ... and {} more", detection.omitted);
             }
-            vscode_dir.join("launch.json")
+        } else {
+            println!("  - {}", detection.type_name);
         }
+    }
+}
+
+/// Reports generated configurations that are missing from, differ in or are
+/// no longer produced for the launch.json at `output_path`
+fn check_launch_json(
+    configs: &[Value],
+    output_path: &Path,
+    json_output: bool,
+) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let staleness = if output_path.exists() {
+        merge::check_document(&read_existing(output_path)?, configs)
+            .map_err(|e| format!("Invalid {}: {}", output_path.display(), e))?
+    } else {
+        merge::check_configurations(&[], configs)
     };
 
-    create_launch_json(&configs, &output_path)?;
-    println!("Created launch.json at {}", output_path.display());
+    if json_output {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "output": output_path,
+                "upToDate": staleness.is_empty(),
+                "missing": staleness.missing,
+                "stale": staleness.stale,
+                "extra": staleness.extra,
+            }))?
+        );
+    } else if staleness.is_empty() {
+        println!("{} is up to date", output_path.display());
+    } else {
+        println!("{} is out of date:", output_path.display());
+        let groups = [
+            ("missing", &staleness.missing),
+            ("stale", &staleness.stale),
+            ("no longer generated", &staleness.extra),
+        ];
+        for (label, names) in groups {
+            for name in names {
+                println!("  {}: {}", label, name);
+            }
+        }
+    }
 
-    Ok(())
+    Ok(if staleness.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_STALE)
+    })
 }
 
-fn create_launch_json(
+/// Renders the launch.json document for `configs`, merged into the existing
+/// file at `output_path` if `merge` is set
+fn render_launch_json(
     configs: &[Value],
     output_path: &Path,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let launch_config = json!({
+    merge: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if merge && output_path.exists() {
+        return Ok(merge::merge_document(&read_existing(output_path)?, configs)
+            .map_err(|e| format!("Invalid {}: {}", output_path.display(), e))?);
+    }
+    Ok(serde_json::to_string_pretty(&json!({
         "version": "0.2.0",
         "configurations": configs
-    });
+    }))?)
+}
+
+/// Renders tasks.json with the tasks referenced by `preLaunchTask` added,
+/// keeping any existing tasks. Returns `None` if the file needs no change.
+fn render_tasks_json(
+    configs: &[Value],
+    tasks_path: &Path,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let existing = if tasks_path.exists() {
+        Some(read_existing(tasks_path)?)
+    } else {
+        None
+    };
+    let defined = existing
+        .as_deref()
+        .map(tasks::defined_labels)
+        .unwrap_or_default();
 
-    if let Some(parent) = output_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)?;
+    let mut required = Vec::new();
+    for label in tasks::referenced_labels(configs) {
+        match tasks::task_definition(&label) {
+            Some(task) => required.push(task),
+            None if !defined.contains(&label) => {
+                eprintln!("Warning: No definition known for task '{}'", label);
+            }
+            None => {}
         }
     }
+    if required.is_empty() {
+        return Ok(None);
+    }
 
-    let mut file = File::create(output_path)?;
-    let formatted = serde_json::to_string_pretty(&launch_config)?;
-    file.write_all(formatted.as_bytes())?;
+    let formatted = match &existing {
+        Some(text) => tasks::merge_tasks_document(text, &required)
+            .map_err(|e| format!("Invalid {}: {}", tasks_path.display(), e))?,
+        None => serde_json::to_string_pretty(&json!({
+            "version": "2.0.0",
+            "tasks": required
+        }))?,
+    };
+    if existing.as_ref() == Some(&formatted) {
+        return Ok(None);
+    }
+
+    Ok(Some(formatted))
+}
 
+/// Reads an existing launch.json or tasks.json
+fn read_existing(path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e).into())
+}
+
+/// Writes `contents` to `path`, creating missing parent directories
+fn write_file(path: &Path, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = File::create(path)?;
+    file.write_all(contents.as_bytes())?;
+
+    Ok(())
+}
+
+/// Prints a unified diff from the current contents of `path` to `contents`;
+/// a missing file counts as empty
+fn print_diff(path: &Path, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let current = if path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+    let name = path.display().to_string();
+    let diff = TextDiff::from_lines(current.as_str(), contents);
+    print!(
+        "{}",
+        diff.unified_diff().context_radius(3).header(&name, &name)
+    );
     Ok(())
 }
@@ -0,0 +1,292 @@
+use std::error::Error;
+
+use serde_json::{Value, json};
+
+use crate::jsonc;
+
+/// Presentation group stamped on every configuration this tool generates, so
+/// that later runs can tell generated entries apart from user-authored ones.
+pub const GENERATED_GROUP: &str = "launch-gen";
+
+/// Marks a configuration as generated by this tool
+pub fn mark_generated(config: &mut Value) {
+    if let Some(map) = config.as_object_mut() {
+        let presentation = map.entry("presentation").or_insert_with(|| json!({}));
+        if let Some(presentation) = presentation.as_object_mut() {
+            presentation.insert("group".to_string(), json!(GENERATED_GROUP));
+        }
+    }
+}
+
+/// Checks whether a configuration carries the generated marker
+pub fn is_generated(config: &Value) -> bool {
+    config
+        .pointer("/presentation/group")
+        .and_then(Value::as_str)
+        == Some(GENERATED_GROUP)
+}
+
+fn config_name(config: &Value) -> Option<&str> {
+    config.get("name").and_then(Value::as_str)
+}
+
+/// What should happen to a single generated configuration during a merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeAction {
+    /// Replace the existing entry at this index
+    Replace(usize),
+    /// Append as a new entry
+    Append,
+    /// Leave out: a user-authored entry with the same name already exists
+    Skip(usize),
+}
+
+/// Decides, for each generated configuration, how it merges into `existing`.
+///
+/// Generated entries are matched by name. An existing entry with the same
+/// name is only replaced when it carries the generated marker; otherwise it
+/// is treated as user-authored and left alone.
+pub fn plan_merge(existing: &[Value], generated: &[Value]) -> Vec<MergeAction> {
+    generated
+        .iter()
+        .map(|config| {
+            let name = config_name(config);
+            match existing
+                .iter()
+                .position(|e| name.is_some() && config_name(e) == name)
+            {
+                Some(index) if is_generated(&existing[index]) => MergeAction::Replace(index),
+                Some(index) => MergeAction::Skip(index),
+                None => MergeAction::Append,
+            }
+        })
+        .collect()
+}
+
+fn warn_skipped(config: &Value) {
+    eprintln!(
+        "Warning: Keeping user-authored configuration '{}'",
+        config_name(config).unwrap_or_default()
+    );
+}
+
+/// Merges generated configurations into an existing list, keeping
+/// user-authored entries untouched and updating generated ones in place
+pub fn merge_configurations(existing: &[Value], generated: &[Value]) -> Vec<Value> {
+    let mut merged = existing.to_vec();
+    for (config, action) in generated.iter().zip(plan_merge(existing, generated)) {
+        match action {
+            MergeAction::Replace(index) => merged[index] = config.clone(),
+            MergeAction::Append => merged.push(config.clone()),
+            MergeAction::Skip(_) => warn_skipped(config),
+        }
+    }
+    merged
+}
+
+/// Merges generated configurations into the text of an existing launch.json.
+///
+/// The document is read as JSONC. Comments, key order and formatting of
+/// everything the merge does not touch are preserved; only replaced entries
+/// are re-serialized and new ones are appended to the array.
+pub fn merge_document(text: &str, generated: &[Value]) -> Result<String, Box<dyn Error>> {
+    let mut document = jsonc::parse(text)?;
+    let existing = document
+        .get("configurations")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut replacements = Vec::new();
+    let mut appended = Vec::new();
+    for (config, action) in generated.iter().zip(plan_merge(&existing, generated)) {
+        match action {
+            // Leave identical entries alone so their formatting survives
+            MergeAction::Replace(index) if existing[index] != *config => {
+                replacements.push((index, config.clone()))
+            }
+            MergeAction::Replace(_) => {}
+            MergeAction::Append => appended.push(config.clone()),
+            MergeAction::Skip(_) => warn_skipped(config),
+        }
+    }
+
+    if let Some(merged) = jsonc::rewrite_array(text, "configurations", &replacements, &appended) {
+        return Ok(merged);
+    }
+
+    // No configurations array to edit in place, so fall back to re-serializing
+    let merged = merge_configurations(&existing, generated);
+    match document.as_object_mut() {
+        Some(map) => {
+            map.insert("configurations".to_string(), json!(merged));
+        }
+        None => {
+            document = json!({
+                "version": "0.2.0",
+                "configurations": merged
+            });
+        }
+    }
+    Ok(serde_json::to_string_pretty(&document)?.replace('\n', jsonc::line_ending(text)))
+}
+
+/// Names of generated configurations that differ from an existing launch.json
+#[derive(Debug, Default)]
+pub struct Staleness {
+    /// Generated but not present
+    pub missing: Vec<String>,
+    /// Present with the generated marker but with different contents
+    pub stale: Vec<String>,
+    /// Present with the generated marker but no longer generated
+    pub extra: Vec<String>,
+}
+
+impl Staleness {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.stale.is_empty() && self.extra.is_empty()
+    }
+}
+
+/// Properties that depend on the machine generating the configuration, such
+/// as the Python interpreter of a local environment, and which `--check`
+/// therefore does not compare
+const MACHINE_SPECIFIC: &[&str] = &["python"];
+
+/// Whether two configurations agree apart from [`MACHINE_SPECIFIC`] properties
+fn same_for_check(existing: &Value, generated: &Value) -> bool {
+    let portable = |config: &Value| {
+        let mut config = config.clone();
+        if let Some(map) = config.as_object_mut() {
+            for key in MACHINE_SPECIFIC {
+                map.remove(*key);
+            }
+        }
+        config
+    };
+    portable(existing) == portable(generated)
+}
+
+/// Compares generated configurations with an existing list. User-authored
+/// entries are ignored, including ones shadowing a generated name.
+pub fn check_configurations(existing: &[Value], generated: &[Value]) -> Staleness {
+    let mut staleness = Staleness::default();
+    for (config, action) in generated.iter().zip(plan_merge(existing, generated)) {
+        let name = config_name(config).unwrap_or_default().to_string();
+        match action {
+            MergeAction::Replace(index) if !same_for_check(&existing[index], config) => {
+                staleness.stale.push(name)
+            }
+            MergeAction::Append => staleness.missing.push(name),
+            MergeAction::Replace(_) | MergeAction::Skip(_) => {}
+        }
+    }
+    staleness.extra = existing
+        .iter()
+        .filter(|e| is_generated(e))
+        .filter_map(config_name)
+        .filter(|name| !generated.iter().any(|c| config_name(c) == Some(name)))
+        .map(String::from)
+        .collect();
+    staleness
+}
+
+/// Compares generated configurations with the text of an existing launch.json
+pub fn check_document(text: &str, generated: &[Value]) -> Result<Staleness, Box<dyn Error>> {
+    let document = jsonc::parse(text)?;
+    let existing = document
+        .get("configurations")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    Ok(check_configurations(&existing, generated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generated(name: &str) -> Value {
+        let mut config = json!({ "name": name, "type": "node" });
+        mark_generated(&mut config);
+        config
+    }
+
+    fn user(name: &str) -> Value {
+        json!({ "name": name, "type": "node" })
+    }
+
+    #[test]
+    fn plan_replaces_generated_skips_user_and_appends_new() {
+        let existing = [user("Mine"), generated("Old"), user("Shadowed")];
+        let configs = [generated("Old"), generated("Shadowed"), generated("New")];
+        assert_eq!(
+            plan_merge(&existing, &configs),
+            [
+                MergeAction::Replace(1),
+                MergeAction::Skip(2),
+                MergeAction::Append
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_appends_unnamed_configurations() {
+        let existing = [json!({ "type": "node" })];
+        let configs = [json!({ "type": "node" })];
+        assert_eq!(plan_merge(&existing, &configs), [MergeAction::Append]);
+    }
+
+    #[test]
+    fn merge_keeps_user_entries_in_place() {
+        let existing = [generated("A"), user("Mine")];
+        let mut updated = generated("A");
+        updated["program"] = json!("a.js");
+        let merged = merge_configurations(&existing, &[updated.clone(), generated("B")]);
+        assert_eq!(merged, [updated, user("Mine"), generated("B")]);
+    }
+
+    #[test]
+    fn merging_a_document_twice_changes_nothing() {
+        let text =
+            "{\n  // keep me\n  \"configurations\": [\n    { \"name\": \"Mine\" },\n  ]\n}\n";
+        let configs = [generated("A"), generated("B")];
+        let once = merge_document(text, &configs).unwrap();
+        let twice = merge_document(&once, &configs).unwrap();
+        assert_eq!(once, twice);
+
+        let mut updated = generated("B");
+        updated["program"] = json!("b.js");
+        let changed = merge_document(&once, &[generated("A"), updated.clone()]).unwrap();
+        assert!(changed.contains("// keep me"));
+        assert_eq!(
+            jsonc::parse(&changed).unwrap()["configurations"],
+            json!([{ "name": "Mine" }, generated("A"), updated])
+        );
+    }
+
+    #[test]
+    fn check_reports_missing_stale_and_extra_entries() {
+        let mut stale = generated("Stale");
+        stale["program"] = json!("old.js");
+        let existing = [generated("Same"), stale, generated("Gone"), user("Mine")];
+        let configs = [
+            generated("Same"),
+            generated("Stale"),
+            generated("New"),
+            generated("Mine"),
+        ];
+        let staleness = check_configurations(&existing, &configs);
+        assert_eq!(staleness.missing, ["New"]);
+        assert_eq!(staleness.stale, ["Stale"]);
+        assert_eq!(staleness.extra, ["Gone"]);
+    }
+
+    #[test]
+    fn check_ignores_the_python_interpreter() {
+        let mut local = generated("Python");
+        local["python"] = json!("${workspaceFolder}/.venv/bin/python");
+        let staleness = check_configurations(&[local], &[generated("Python")]);
+        assert!(staleness.is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use ignore::{WalkBuilder, overrides::OverrideBuilder};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::cargo::{self, Package};
+use crate::python;
+
+/// Vendored dependencies, build output and tool caches, which are skipped
+/// even when no ignore file mentions them
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    "node_modules",
+    "bower_components",
+    "target",
+    ".venv",
+    "venv",
+    "__pycache__",
+    ".tox",
+    ".nox",
+    ".mypy_cache",
+    ".pytest_cache",
+    "build",
+    "dist",
+    "out",
+];
+
+/// Files whose contents are read during the scan and kept for providers,
+/// along with pip requirements files
+const MANIFESTS: &[&str] = &[
+    "requirements.txt",
+    "pyproject.toml",
+    "setup.cfg",
+    "Pipfile",
+    "package.json",
+    "Cargo.toml",
+    "CMakeLists.txt",
+    "Makefile",
+];
+
+/// Controls which part of the project tree is scanned
+pub struct ScanOptions {
+    /// How many directory levels below the root are scanned
+    pub max_depth: usize,
+    /// Glob patterns of paths to leave out, relative to the root
+    pub exclude: Vec<String>,
+    /// File names read like the built-in manifests
+    pub manifests: Vec<String>,
+    /// Also look up Python interpreters outside the project, such as Poetry,
+    /// conda and pyenv environments
+    pub external_python: bool,
+}
+
+/// Index of the project configurations are generated for, built from a
+/// single walk of the tree.
+///
+/// Manifests are read once during the scan; other files are only read on
+/// demand by [`Project::find_source`].
+pub struct Project {
+    /// Directory that is scanned and that VS Code opens as `${workspaceFolder}`
+    pub root: PathBuf,
+    files: Vec<PathBuf>,
+    by_extension: HashMap<String, Vec<PathBuf>>,
+    manifests: BTreeMap<PathBuf, String>,
+    packages: OnceLock<Vec<Package>>,
+    python: OnceLock<Option<String>>,
+    external_python: bool,
+}
+
+impl Project {
+    /// Walks the tree below `root`, honouring `.gitignore`/`.ignore` files,
+    /// [`SKIPPED_DIRS`] and the exclude patterns
+    pub fn scan(root: &Path, options: &ScanOptions) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut overrides = OverrideBuilder::new(root);
+        for pattern in &options.exclude {
+            overrides.add(&format!("!{}", pattern))?;
+        }
+
+        let mut project = Project {
+            root: root.to_path_buf(),
+            files: Vec::new(),
+            by_extension: HashMap::new(),
+            manifests: BTreeMap::new(),
+            packages: OnceLock::new(),
+            python: OnceLock::new(),
+            external_python: options.external_python,
+        };
+
+        let entries = WalkBuilder::new(root)
+            .max_depth(Some(options.max_depth))
+            .hidden(false)
+            .require_git(false)
+            // A fixed order keeps the reported evidence the same on every machine
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .overrides(overrides.build()?)
+            .filter_entry(|entry| {
+                !(entry.file_type().is_some_and(|t| t.is_dir())
+                    && SKIPPED_DIRS.iter().any(|dir| entry.file_name() == *dir))
+            })
+            .build()
+            .filter_map(|e| e.ok())
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()));
+
+        for entry in entries {
+            let path = entry.into_path();
+            if let Some(ext) = path.extension() {
+                project
+                    .by_extension
+                    .entry(ext.to_string_lossy().to_string())
+                    .or_default()
+                    .push(path.clone());
+            }
+            let is_manifest = path.file_name().is_some_and(|name| {
+                MANIFESTS.iter().any(|m| name == *m)
+                    || options.manifests.iter().any(|m| name == m.as_str())
+            }) || python::is_requirements_file(&path);
+            if is_manifest && let Ok(content) = fs::read_to_string(&path) {
+                project.manifests.insert(path.clone(), content);
+            }
+            project.files.push(path);
+        }
+
+        Ok(project)
+    }
+
+    /// Every file found by the scan
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Files with the given extension (without the dot)
+    pub fn files_with_extension(&self, ext: &str) -> &[PathBuf] {
+        self.by_extension.get(ext).map_or(&[], Vec::as_slice)
+    }
+
+    /// All manifests found by the scan with their contents
+    pub fn manifests(&self) -> impl Iterator<Item = (&Path, &str)> {
+        self.manifests
+            .iter()
+            .map(|(path, content)| (path.as_path(), content.as_str()))
+    }
+
+    /// Path and contents of the manifest with this file name closest to the root
+    pub fn manifest(&self, file_name: &str) -> Option<(&Path, &str)> {
+        self.manifests
+            .iter()
+            .filter(|(path, _)| path.file_name().is_some_and(|name| name == file_name))
+            .min_by_key(|(path, _)| path.components().count())
+            .map(|(path, content)| (path.as_path(), content.as_str()))
+    }
+
+    /// Contents of the manifest at `path`, if the scan read it
+    pub fn manifest_at(&self, path: &Path) -> Option<&str> {
+        self.manifests.get(path).map(String::as_str)
+    }
+
+    /// Cargo packages of the project, loaded on first use
+    pub fn cargo_packages(&self) -> &[Package] {
+        self.packages
+            .get_or_init(|| cargo::load_packages(&self.root))
+    }
+
+    /// Interpreter of the project's Python environment, looked up on first use
+    pub fn python_interpreter(&self) -> Option<&str> {
+        self.python
+            .get_or_init(|| {
+                let pyproject = self.manifest_at(&self.root.join("pyproject.toml"));
+                python::find_interpreter(&self.root, pyproject, self.external_python)
+            })
+            .as_deref()
+    }
+
+    /// Finds the first file with the given extension whose content matches
+    /// `predicate`, reading files in parallel when the `parallel` feature is on
+    pub fn find_source(&self, ext: &str, predicate: impl Fn(&str) -> bool + Sync) -> Option<&Path> {
+        let matches = |path: &&PathBuf| fs::read_to_string(path).is_ok_and(|c| predicate(&c));
+        #[cfg(feature = "parallel")]
+        let found = self
+            .files_with_extension(ext)
+            .par_iter()
+            .find_first(matches);
+        #[cfg(not(feature = "parallel"))]
+        let found = self.files_with_extension(ext).iter().find(matches);
+        found.map(PathBuf::as_path)
+    }
+}
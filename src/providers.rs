@@ -1,91 +1,170 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 
-use crate::types::ConfigProvider;
-use serde_json::{Value, json};
+use crate::cargo::{Package, Target, TargetKind};
+use crate::launch::{
+    CargoFilter, CargoOptions, ChromeOptions, CppdbgOptions, Debugger, LaunchConfig, LldbOptions,
+    NodeOptions, PythonOptions, SetupCommand,
+};
+use crate::project::Project;
+use crate::python;
+use crate::types::{ConfigProvider, Params};
+
+/// Converts string literals into owned strings for list-valued properties
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
 
 pub struct PythonConfigProvider;
 impl ConfigProvider for PythonConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "python"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "Python: Current File",
-            "type": "debugpy",
-            "request": "launch",
-            "program": "${file}",
-            "console": "integratedTerminal",
-            "justMyCode": true,
-            "args": []
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["args", "cwd"]
+    }
+
+    fn get_configs(&self, project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        vec![LaunchConfig {
+            program: Some("${file}".to_string()),
+            args: Some(params.list("args").unwrap_or_default()),
+            cwd: params.get("cwd").map(String::from),
+            ..LaunchConfig::new(
+                "Python: Current File",
+                Debugger::Debugpy(PythonOptions {
+                    python: project.python_interpreter().map(String::from),
+                    console: Some("integratedTerminal".to_string()),
+                    just_my_code: Some(true),
+                    ..Default::default()
+                }),
+            )
+        }]
     }
 
     fn can_detect_from_file(&self, path: &Path) -> bool {
-        path.extension().map_or(false, |ext| ext == "py")
+        path.extension().is_some_and(|ext| ext == "py")
     }
 }
 
 pub struct PythonModuleConfigProvider;
 impl ConfigProvider for PythonModuleConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "python-module"
     }
 
-    fn get_config(&self, params: Option<&str>) -> Value {
-        let module_name = params.unwrap_or("app");
-        json!({
-            "name": format!("Python: Module {}", module_name),
-            "type": "debugpy",
-            "request": "launch",
-            "module": module_name,
-            "console": "integratedTerminal",
-            "justMyCode": true,
-            "args": []
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["module", "args", "cwd"]
+    }
+
+    fn get_configs(&self, project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let module_name = params.get("module").unwrap_or("app");
+        vec![LaunchConfig {
+            args: Some(params.list("args").unwrap_or_default()),
+            cwd: params.get("cwd").map(String::from),
+            ..LaunchConfig::new(
+                format!("Python: Module {}", module_name),
+                Debugger::Debugpy(PythonOptions {
+                    python: project.python_interpreter().map(String::from),
+                    module: Some(module_name.to_string()),
+                    console: Some("integratedTerminal".to_string()),
+                    just_my_code: Some(true),
+                    ..Default::default()
+                }),
+            )
+        }]
     }
 
     fn can_detect_from_file(&self, _path: &Path) -> bool {
-        false // Not auto-detected from file extension
+        false // Detection adds python-module:pytest for a pytest dependency
     }
+}
 
-    fn can_detect_from_content(&self, filename: &str, content: &str) -> bool {
-        if filename == "requirements.txt" {
-            if content.contains("django") {
-                return true;
-            }
-            if content.contains("pytest") {
-                return true;
+pub struct PythonScriptsConfigProvider;
+impl ConfigProvider for PythonScriptsConfigProvider {
+    fn name(&self) -> &str {
+        "python-scripts"
+    }
+
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["script", "args", "cwd"]
+    }
+
+    fn get_configs(&self, project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let mut configs: Vec<LaunchConfig> = Vec::new();
+        for file_name in python::MANIFESTS {
+            let Some((_, content)) = project.manifest(file_name) else {
+                continue;
+            };
+            for entry_point in python::entry_points(file_name, content) {
+                if params.get("script").is_some_and(|s| s != entry_point.name) {
+                    continue;
+                }
+                let name = format!("Python: Script {}", entry_point.name);
+                if configs.iter().any(|c| c.name == name) {
+                    continue;
+                }
+                configs.push(LaunchConfig {
+                    args: Some(params.list("args").unwrap_or_default()),
+                    cwd: params.get("cwd").map(String::from),
+                    ..LaunchConfig::new(
+                        name,
+                        Debugger::Debugpy(PythonOptions {
+                            python: project.python_interpreter().map(String::from),
+                            code: Some(entry_point.launch_code()),
+                            console: Some("integratedTerminal".to_string()),
+                            just_my_code: Some(true),
+                            ..Default::default()
+                        }),
+                    )
+                });
             }
         }
-        false
+        configs
+    }
+
+    fn no_configs_reason(&self, project: &Project) -> String {
+        format!(
+            "no console scripts declared in the Python manifests under {}",
+            project.root.display()
+        )
+    }
+
+    fn can_detect_from_file(&self, _path: &Path) -> bool {
+        false // Detected from the scripts declared in pyproject.toml or setup.cfg
     }
 }
 
 pub struct FlaskConfigProvider;
 impl ConfigProvider for FlaskConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "flask"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "Python: Flask",
-            "type": "python",
-            "request": "launch",
-            "module": "flask",
-            "env": {
-                "FLASK_APP": "app.py",
-                "FLASK_DEBUG": "1"
-            },
-            "args": [
-                "run",
-                "--no-debugger",
-                "--no-reload"
-            ],
-            "jinja": true,
-            "justMyCode": true
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["app"]
+    }
+
+    fn get_configs(&self, project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let app = params.get("app").unwrap_or("app.py");
+        let env = BTreeMap::from([
+            ("FLASK_APP".to_string(), app.to_string()),
+            ("FLASK_DEBUG".to_string(), "1".to_string()),
+        ]);
+        vec![LaunchConfig {
+            args: Some(strings(&["run", "--no-debugger", "--no-reload"])),
+            ..LaunchConfig::new(
+                "Python: Flask",
+                Debugger::Python(PythonOptions {
+                    python: project.python_interpreter().map(String::from),
+                    module: Some("flask".to_string()),
+                    env: Some(env),
+                    jinja: Some(true),
+                    just_my_code: Some(true),
+                    ..Default::default()
+                }),
+            )
+        }]
     }
 
     fn can_detect_from_file(&self, _path: &Path) -> bool {
@@ -93,28 +172,162 @@ impl ConfigProvider for FlaskConfigProvider {
     }
 
     fn can_detect_from_content(&self, filename: &str, content: &str) -> bool {
-        filename == "requirements.txt" && content.contains("flask")
+        python::declares_dependency(filename, content, "flask")
     }
 }
 
+pub struct DjangoConfigProvider;
+impl ConfigProvider for DjangoConfigProvider {
+    fn name(&self) -> &str {
+        "django"
+    }
+
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["settings"]
+    }
+
+    fn get_configs(&self, project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let Some((manage, content)) = project.manifest("manage.py") else {
+            return Vec::new();
+        };
+        let relative = manage.strip_prefix(&project.root).unwrap_or(manage);
+        let program = format!(
+            "${{workspaceFolder}}/{}",
+            relative.to_string_lossy().replace('\\', "/")
+        );
+        // manage.py resolves the project's apps relative to its own directory
+        let cwd = relative
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(|dir| {
+                format!(
+                    "${{workspaceFolder}}/{}",
+                    dir.to_string_lossy().replace('\\', "/")
+                )
+            });
+        let settings = params
+            .get("settings")
+            .map(String::from)
+            .or_else(|| django_settings(project, manage, content));
+        let env = settings
+            .map(|settings| BTreeMap::from([("DJANGO_SETTINGS_MODULE".to_string(), settings)]));
+
+        let commands: [(&str, &[&str]); 3] = [
+            // The autoreloader runs the server in a child process the
+            // debugger does not attach to
+            ("Django: Runserver", &["runserver", "--noreload"]),
+            ("Django: Test", &["test"]),
+            ("Django: Shell", &["shell"]),
+        ];
+        commands
+            .iter()
+            .map(|(name, args)| LaunchConfig {
+                program: Some(program.clone()),
+                args: Some(strings(args)),
+                cwd: cwd.clone(),
+                ..LaunchConfig::new(
+                    *name,
+                    Debugger::Debugpy(PythonOptions {
+                        python: project.python_interpreter().map(String::from),
+                        console: Some("integratedTerminal".to_string()),
+                        env: env.clone(),
+                        django: Some(true),
+                        just_my_code: Some(true),
+                        ..Default::default()
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    fn no_configs_reason(&self, project: &Project) -> String {
+        format!("no manage.py found under {}", project.root.display())
+    }
+
+    fn can_detect_from_file(&self, _path: &Path) -> bool {
+        false // Detected from manage.py, which the configurations run
+    }
+
+    fn can_detect_from_content(&self, filename: &str, content: &str) -> bool {
+        filename == "manage.py"
+            && ["DJANGO_SETTINGS_MODULE", "django.core.management"]
+                .iter()
+                .any(|marker| content.contains(marker))
+    }
+
+    fn manifest_files(&self) -> Vec<String> {
+        vec!["manage.py".to_string()]
+    }
+}
+
+/// The settings module manage.py sets as the default for
+/// `DJANGO_SETTINGS_MODULE`, or else the package next to manage.py that
+/// holds a `settings.py` or a `settings` package
+fn django_settings(project: &Project, manage: &Path, content: &str) -> Option<String> {
+    let assigned = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && line.contains("DJANGO_SETTINGS_MODULE"))
+        // The first string literal is the variable name, the second its value
+        .find_map(|line| string_literals(line).nth(1));
+    if assigned.is_some() {
+        return assigned;
+    }
+
+    let dir = manage.parent()?;
+    project.files_with_extension("py").iter().find_map(|path| {
+        let parts: Vec<&str> = path
+            .strip_prefix(dir)
+            .ok()?
+            .iter()
+            .map(|part| part.to_str())
+            .collect::<Option<_>>()?;
+        match parts.as_slice() {
+            [package, "settings.py"] | [package, "settings", "__init__.py"] => {
+                Some(format!("{}.settings", package))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// The contents of the quoted strings in a line of Python
+fn string_literals(line: &str) -> impl Iterator<Item = String> + '_ {
+    let mut rest = line;
+    std::iter::from_fn(move || {
+        let start = rest.find(['"', '\''])?;
+        let quote = rest[start..].chars().next()?;
+        let len = rest[start + 1..].find(quote)?;
+        let literal = rest[start + 1..start + 1 + len].to_string();
+        rest = &rest[start + len + 2..];
+        Some(literal)
+    })
+}
+
 pub struct FastApiConfigProvider;
 impl ConfigProvider for FastApiConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "fastapi"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "Python: FastAPI",
-            "type": "python",
-            "request": "launch",
-            "module": "uvicorn",
-            "args": [
-                "app.main:app",
-                "--reload"
-            ],
-            "justMyCode": true
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["app"]
+    }
+
+    fn get_configs(&self, project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let app = params.get("app").unwrap_or("app.main:app");
+        vec![LaunchConfig {
+            args: Some(strings(&[app, "--reload"])),
+            ..LaunchConfig::new(
+                "Python: FastAPI",
+                Debugger::Python(PythonOptions {
+                    python: project.python_interpreter().map(String::from),
+                    module: Some("uvicorn".to_string()),
+                    just_my_code: Some(true),
+                    ..Default::default()
+                }),
+            )
+        }]
     }
 
     fn can_detect_from_file(&self, _path: &Path) -> bool {
@@ -122,178 +335,314 @@ impl ConfigProvider for FastApiConfigProvider {
     }
 
     fn can_detect_from_content(&self, filename: &str, content: &str) -> bool {
-        filename == "requirements.txt" && content.contains("fastapi")
+        python::declares_dependency(filename, content, "fastapi")
     }
 }
 
 pub struct JavaScriptConfigProvider;
 impl ConfigProvider for JavaScriptConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "javascript"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "JavaScript: Launch Chrome",
-            "type": "chrome",
-            "request": "launch",
-            "url": "http://localhost:3000",
-            "webRoot": "${workspaceFolder}"
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["url"]
+    }
+
+    fn get_configs(&self, _project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        vec![LaunchConfig::new(
+            "JavaScript: Launch Chrome",
+            Debugger::Chrome(ChromeOptions {
+                url: Some(
+                    params
+                        .get("url")
+                        .unwrap_or("http://localhost:3000")
+                        .to_string(),
+                ),
+                web_root: Some("${workspaceFolder}".to_string()),
+            }),
+        )]
     }
 
     fn can_detect_from_file(&self, path: &Path) -> bool {
-        path.extension().map_or(false, |ext| ext == "js")
+        path.extension().is_some_and(|ext| ext == "js")
     }
 }
 
 pub struct NodeConfigProvider;
 impl ConfigProvider for NodeConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "node"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "Node.js: Current File",
-            "type": "node",
-            "request": "launch",
-            "program": "${file}",
-            "console": "integratedTerminal"
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["program", "args"]
+    }
+
+    fn get_configs(&self, _project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        vec![LaunchConfig {
+            program: Some(params.get("program").unwrap_or("${file}").to_string()),
+            args: params.list("args"),
+            ..LaunchConfig::new(
+                "Node.js: Current File",
+                Debugger::Node(NodeOptions {
+                    console: Some("integratedTerminal".to_string()),
+                    ..Default::default()
+                }),
+            )
+        }]
     }
 
     fn can_detect_from_file(&self, path: &Path) -> bool {
-        path.file_name()
-            .map_or(false, |name| name == "package.json")
+        path.file_name().is_some_and(|name| name == "package.json")
     }
 }
 
 pub struct TypeScriptConfigProvider;
 impl ConfigProvider for TypeScriptConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "typescript"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "TypeScript: Current File",
-            "type": "node",
-            "request": "launch",
-            "program": "${file}",
-            "preLaunchTask": "tsc: build - tsconfig.json",
-            "outFiles": ["${workspaceFolder}/dist/**/*.js"]
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["program", "args"]
+    }
+
+    fn get_configs(&self, _project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        vec![LaunchConfig {
+            program: Some(params.get("program").unwrap_or("${file}").to_string()),
+            args: params.list("args"),
+            pre_launch_task: Some("tsc: build - tsconfig.json".to_string()),
+            ..LaunchConfig::new(
+                "TypeScript: Current File",
+                Debugger::Node(NodeOptions {
+                    out_files: Some(strings(&["${workspaceFolder}/dist/**/*.js"])),
+                    ..Default::default()
+                }),
+            )
+        }]
     }
 
     fn can_detect_from_file(&self, path: &Path) -> bool {
-        if let Some(ext) = path.extension() {
-            if ext == "ts" {
-                return true;
-            }
+        if let Some(ext) = path.extension()
+            && ext == "ts"
+        {
+            return true;
         }
-        path.file_name()
-            .map_or(false, |name| name == "tsconfig.json")
+        path.file_name().is_some_and(|name| name == "tsconfig.json")
+    }
+}
+
+/// Names a Rust configuration, qualifying it with the package name when the
+/// project has several packages so that workspace members stay distinct
+fn rust_config_name(base: &str, package: &Package, packages: &[Package]) -> String {
+    if packages.len() > 1 {
+        format!("{} ({})", base, package.name)
+    } else {
+        base.to_string()
+    }
+}
+
+/// Why a Rust provider found no targets of the given kind
+fn no_targets_reason(kind: &str, project: &Project) -> String {
+    if project.cargo_packages().is_empty() {
+        format!("no Cargo package found under {}", project.root.display())
+    } else {
+        format!(
+            "no {} targets in the Cargo packages under {}",
+            kind,
+            project.root.display()
+        )
+    }
+}
+
+/// Builds a CodeLLDB configuration that has cargo build `target` and then
+/// launches the resulting executable with `args`
+fn cargo_target_config(
+    name: String,
+    cargo_args: &[&str],
+    target: &Target,
+    args: &[&str],
+) -> LaunchConfig {
+    LaunchConfig {
+        args: Some(strings(args)),
+        cwd: Some("${workspaceFolder}".to_string()),
+        ..LaunchConfig::new(
+            name,
+            Debugger::Lldb(LldbOptions {
+                cargo: Some(CargoOptions {
+                    args: strings(cargo_args),
+                    filter: Some(CargoFilter {
+                        name: target.name.clone(),
+                        kind: target.cargo_kind.clone(),
+                    }),
+                }),
+                ..Default::default()
+            }),
+        )
+    }
+}
+
+/// Builds a CodeLLDB configuration that has cargo produce the executable
+/// without naming a specific target
+fn cargo_build_config(name: String, cargo_args: &[&str]) -> LaunchConfig {
+    LaunchConfig {
+        args: Some(Vec::new()),
+        cwd: Some("${workspaceFolder}".to_string()),
+        ..LaunchConfig::new(
+            name,
+            Debugger::Lldb(LldbOptions {
+                cargo: Some(CargoOptions {
+                    args: strings(cargo_args),
+                    filter: None,
+                }),
+                ..Default::default()
+            }),
+        )
     }
 }
 
 pub struct RustConfigProvider;
 impl ConfigProvider for RustConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "rust"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "Rust: Debug Binary",
-            "type": "lldb",
-            "request": "launch",
-            "program": "${workspaceFolder}/target/debug/${workspaceFolderBasename}",
-            "args": [],
-            "cwd": "${workspaceFolder}",
-            "preLaunchTask": "cargo build"
-        })
+    fn get_configs(&self, project: &Project, _params: &Params) -> Vec<LaunchConfig> {
+        let packages = project.cargo_packages();
+        let configs: Vec<LaunchConfig> = packages
+            .iter()
+            .flat_map(|package| {
+                package
+                    .targets_of(TargetKind::Bin)
+                    .map(move |bin| (package, bin))
+            })
+            .map(|(package, bin)| {
+                cargo_target_config(
+                    rust_config_name(
+                        &format!("Rust: Debug Binary '{}'", bin.name),
+                        package,
+                        packages,
+                    ),
+                    &["build", "-p", &package.name, "--bin", &bin.name],
+                    bin,
+                    &[],
+                )
+            })
+            .collect();
+        if !configs.is_empty() {
+            return configs;
+        }
+
+        // Without any known binary target, fall back to the conventional path
+        vec![LaunchConfig {
+            program: Some("${workspaceFolder}/target/debug/${workspaceFolderBasename}".to_string()),
+            args: Some(Vec::new()),
+            cwd: Some("${workspaceFolder}".to_string()),
+            pre_launch_task: Some("cargo build".to_string()),
+            ..LaunchConfig::new("Rust: Debug Binary", Debugger::Lldb(LldbOptions::default()))
+        }]
     }
 
     fn can_detect_from_file(&self, path: &Path) -> bool {
-        if let Some(ext) = path.extension() {
-            if ext == "rs" {
-                return true;
-            }
+        if let Some(ext) = path.extension()
+            && ext == "rs"
+        {
+            return true;
         }
-        path.file_name().map_or(false, |name| name == "Cargo.toml")
+        path.file_name().is_some_and(|name| name == "Cargo.toml")
     }
 }
 
 pub struct RustLibConfigProvider;
 impl ConfigProvider for RustLibConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "rust-lib"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "Rust: Debug Library",
-            "type": "lldb",
-            "request": "launch",
-            "cargo": {
-                "args": [
-                    "build",
-                    "--lib"
-                ]
-            },
-            "args": [],
-            "cwd": "${workspaceFolder}"
-        })
+    fn get_configs(&self, project: &Project, _params: &Params) -> Vec<LaunchConfig> {
+        let packages = project.cargo_packages();
+        let configs: Vec<LaunchConfig> = packages
+            .iter()
+            .filter(|package| package.targets_of(TargetKind::Lib).next().is_some())
+            .map(|package| {
+                cargo_build_config(
+                    rust_config_name("Rust: Debug Library", package, packages),
+                    &["build", "-p", &package.name, "--lib"],
+                )
+            })
+            .collect();
+        // Packages without a lib target get no library configuration; only
+        // guess one when the Cargo targets are unknown
+        if !packages.is_empty() {
+            return configs;
+        }
+
+        vec![cargo_build_config(
+            "Rust: Debug Library".to_string(),
+            &["build", "--lib"],
+        )]
     }
 
-    fn can_detect_from_file(&self, path: &Path) -> bool {
-        // Detect if this is likely a library project
-        if path.file_name().map_or(false, |name| name == "Cargo.toml") {
-            if let Ok(content) = std::fs::read_to_string(path) {
-                return content.contains("[lib]") || !content.contains("[[bin]]");
-            }
-        }
-        path.file_name().map_or(false, |name| name == "lib.rs")
+    fn no_configs_reason(&self, project: &Project) -> String {
+        no_targets_reason("library", project)
     }
 
-    fn can_detect_from_content(&self, filename: &str, content: &str) -> bool {
-        filename == "Cargo.toml" && (content.contains("[lib]") || !content.contains("[[bin]]"))
+    fn can_detect_from_file(&self, path: &Path) -> bool {
+        // Cargo.toml is covered by the lib targets detection reads from cargo
+        path.file_name().is_some_and(|name| name == "lib.rs")
     }
 }
 
 pub struct RustTestConfigProvider;
 impl ConfigProvider for RustTestConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "rust-test"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "Rust: Debug Tests",
-            "type": "lldb",
-            "request": "launch",
-            "cargo": {
-                "args": [
-                    "test",
-                    "--no-run"
-                ]
-            },
-            "args": [],
-            "cwd": "${workspaceFolder}"
-        })
-    }
-
-    fn can_detect_from_file(&self, path: &Path) -> bool {
-        if let Some(ext) = path.extension() {
-            if ext == "rs" {
-                // Check if the file contains test modules or functions
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    return content.contains("#[test]") || content.contains("mod test");
+    fn get_configs(&self, project: &Project, _params: &Params) -> Vec<LaunchConfig> {
+        let packages = project.cargo_packages();
+        let mut configs = Vec::new();
+        for package in packages {
+            // One configuration per test binary: unit tests of the lib and of
+            // each bin, and each integration test target
+            for target in &package.targets {
+                let (base, filter) = match target.kind {
+                    TargetKind::Lib => ("Rust: Debug Unit Tests in Library".to_string(), "--lib"),
+                    TargetKind::Bin => (
+                        format!("Rust: Debug Unit Tests in Binary '{}'", target.name),
+                        "--bin",
+                    ),
+                    TargetKind::Test => (
+                        format!("Rust: Debug Integration Test '{}'", target.name),
+                        "--test",
+                    ),
+                    TargetKind::Example | TargetKind::Bench => continue,
+                };
+                let mut cargo_args = vec!["test", "--no-run", "-p", &package.name, filter];
+                if target.kind != TargetKind::Lib {
+                    cargo_args.push(&target.name);
                 }
+                configs.push(cargo_target_config(
+                    rust_config_name(&base, package, packages),
+                    &cargo_args,
+                    target,
+                    &[],
+                ));
             }
         }
-        false
+        if !configs.is_empty() {
+            return configs;
+        }
+
+        vec![cargo_build_config(
+            "Rust: Debug Tests".to_string(),
+            &["test", "--no-run"],
+        )]
+    }
+
+    fn can_detect_from_file(&self, _path: &Path) -> bool {
+        false // Detected from test targets and by scanning source contents
     }
 
     fn can_detect_from_content(&self, filename: &str, content: &str) -> bool {
@@ -301,61 +650,112 @@ impl ConfigProvider for RustTestConfigProvider {
     }
 }
 
+pub struct RustExampleConfigProvider;
+impl ConfigProvider for RustExampleConfigProvider {
+    fn name(&self) -> &str {
+        "rust-example"
+    }
+
+    fn get_configs(&self, project: &Project, _params: &Params) -> Vec<LaunchConfig> {
+        let packages = project.cargo_packages();
+        packages
+            .iter()
+            .flat_map(|package| {
+                package
+                    .targets_of(TargetKind::Example)
+                    .map(move |example| (package, example))
+            })
+            .map(|(package, example)| {
+                cargo_target_config(
+                    rust_config_name(
+                        &format!("Rust: Debug Example '{}'", example.name),
+                        package,
+                        packages,
+                    ),
+                    &["build", "-p", &package.name, "--example", &example.name],
+                    example,
+                    &[],
+                )
+            })
+            .collect()
+    }
+
+    fn no_configs_reason(&self, project: &Project) -> String {
+        no_targets_reason("example", project)
+    }
+
+    fn can_detect_from_file(&self, _path: &Path) -> bool {
+        false // Detected from the Cargo targets
+    }
+}
+
+pub struct RustBenchConfigProvider;
+impl ConfigProvider for RustBenchConfigProvider {
+    fn name(&self) -> &str {
+        "rust-bench"
+    }
+
+    fn get_configs(&self, project: &Project, _params: &Params) -> Vec<LaunchConfig> {
+        let packages = project.cargo_packages();
+        packages
+            .iter()
+            .flat_map(|package| {
+                package
+                    .targets_of(TargetKind::Bench)
+                    .map(move |bench| (package, bench))
+            })
+            .map(|(package, bench)| {
+                cargo_target_config(
+                    rust_config_name(
+                        &format!("Rust: Debug Benchmark '{}'", bench.name),
+                        package,
+                        packages,
+                    ),
+                    &[
+                        "bench",
+                        "--no-run",
+                        "-p",
+                        &package.name,
+                        "--bench",
+                        &bench.name,
+                    ],
+                    bench,
+                    // Without --bench the harness only smoke-tests the benchmarks
+                    &["--bench"],
+                )
+            })
+            .collect()
+    }
+
+    fn no_configs_reason(&self, project: &Project) -> String {
+        no_targets_reason("bench", project)
+    }
+
+    fn can_detect_from_file(&self, _path: &Path) -> bool {
+        false // Detected from the Cargo targets
+    }
+}
+
 pub struct RustAllConfigProvider;
 impl ConfigProvider for RustAllConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "rust-all"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "configurations": [
-                {
-                    "name": "Rust: Debug Binary",
-                    "type": "lldb",
-                    "request": "launch",
-                    "program": "${workspaceFolder}/target/debug/${workspaceFolderBasename}",
-                    "args": [],
-                    "cwd": "${workspaceFolder}",
-                    "preLaunchTask": "cargo build"
-                },
-                {
-                    "name": "Rust: Debug Library",
-                    "type": "lldb",
-                    "request": "launch",
-                    "cargo": {
-                        "args": [
-                            "build",
-                            "--lib"
-                        ]
-                    },
-                    "args": [],
-                    "cwd": "${workspaceFolder}"
-                },
-                {
-                    "name": "Rust: Debug Tests",
-                    "type": "lldb",
-                    "request": "launch",
-                    "cargo": {
-                        "args": [
-                            "test",
-                            "--no-run"
-                        ]
-                    },
-                    "args": [],
-                    "cwd": "${workspaceFolder}"
-                }
-            ]
-        })
+    fn get_configs(&self, project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let mut configs = RustConfigProvider.get_configs(project, params);
+        configs.extend(RustLibConfigProvider.get_configs(project, params));
+        configs.extend(RustTestConfigProvider.get_configs(project, params));
+        configs
     }
 
     fn can_detect_from_file(&self, path: &Path) -> bool {
-        if let Some(ext) = path.extension() {
-            if ext == "rs" {
-                return true;
-            }
+        if let Some(ext) = path.extension()
+            && ext == "rs"
+        {
+            return true;
         }
-        path.file_name().map_or(false, |name| name == "Cargo.toml")
+        path.file_name().is_some_and(|name| name == "Cargo.toml")
     }
 }
 
@@ -384,31 +784,38 @@ fn detect_cpp_file(path: &Path) -> bool {
 
 pub struct CppGdbConfigProvider;
 impl ConfigProvider for CppGdbConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "cpp-gdb"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "C++: GDB",
-            "type": "cppdbg",
-            "request": "launch",
-            "program": "${workspaceFolder}/build/${fileBasenameNoExtension}",
-            "args": [],
-            "stopAtEntry": false,
-            "cwd": "${workspaceFolder}",
-            "environment": [],
-            "externalConsole": false,
-            "MIMode": "gdb",
-            "setupCommands": [
-                {
-                    "description": "Enable pretty-printing for gdb",
-                    "text": "-enable-pretty-printing",
-                    "ignoreFailures": true
-                }
-            ],
-            "preLaunchTask": "C/C++: Build active file"
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["program", "args"]
+    }
+
+    fn get_configs(&self, _project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let program = params
+            .get("program")
+            .unwrap_or("${fileDirname}/${fileBasenameNoExtension}");
+        vec![LaunchConfig {
+            program: Some(program.to_string()),
+            args: Some(params.list("args").unwrap_or_default()),
+            cwd: Some("${workspaceFolder}".to_string()),
+            pre_launch_task: Some("C/C++: Build active file".to_string()),
+            ..LaunchConfig::new(
+                "C++: GDB",
+                Debugger::Cppdbg(CppdbgOptions {
+                    stop_at_entry: Some(false),
+                    environment: Some(Vec::new()),
+                    external_console: Some(false),
+                    mi_mode: Some("gdb".to_string()),
+                    setup_commands: Some(vec![SetupCommand {
+                        description: "Enable pretty-printing for gdb".to_string(),
+                        text: "-enable-pretty-printing".to_string(),
+                        ignore_failures: true,
+                    }]),
+                }),
+            )
+        }]
     }
 
     fn can_detect_from_file(&self, path: &Path) -> bool {
@@ -418,23 +825,25 @@ impl ConfigProvider for CppGdbConfigProvider {
 
 pub struct CppLldbConfigProvider;
 impl ConfigProvider for CppLldbConfigProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "cpp-lldb"
     }
 
-    fn get_config(&self, _params: Option<&str>) -> Value {
-        json!({
-            "name": "C++: LLDB",
-            "type": "lldb",
-            "request": "launch",
-            "program": "${workspaceFolder}/build/${fileBasenameNoExtension}",
-            "args": [],
-            "stopAtEntry": false,
-            "cwd": "${workspaceFolder}",
-            "environment": [],
-            "externalConsole": false,
-            "preLaunchTask": "C/C++: Build active file"
-        })
+    fn param_keys(&self) -> Vec<&str> {
+        vec!["program", "args"]
+    }
+
+    fn get_configs(&self, _project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let program = params
+            .get("program")
+            .unwrap_or("${fileDirname}/${fileBasenameNoExtension}");
+        vec![LaunchConfig {
+            program: Some(program.to_string()),
+            args: Some(params.list("args").unwrap_or_default()),
+            cwd: Some("${workspaceFolder}".to_string()),
+            pre_launch_task: Some("C/C++: Build active file".to_string()),
+            ..LaunchConfig::new("C++: LLDB", Debugger::Lldb(LldbOptions::default()))
+        }]
     }
 
     fn can_detect_from_file(&self, path: &Path) -> bool {
@@ -0,0 +1,890 @@
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use toml::{Table, Value};
+
+/// Python manifests that declare dependencies, besides requirements files
+pub const MANIFESTS: &[&str] = &["pyproject.toml", "setup.cfg", "Pipfile"];
+
+/// Normalizes a distribution name as pip compares them (PEP 503): lowercase,
+/// with runs of `-`, `_` and `.` replaced by a single `-`
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Extracts the normalized distribution name from a PEP 508 requirement such
+/// as `Flask[async]>=2.0; python_version >= "3.8"`
+pub fn requirement_name(requirement: &str) -> Option<String> {
+    let requirement = requirement.trim_start();
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let name = &requirement[..end];
+    if name.is_empty() {
+        None
+    } else {
+        Some(normalize_name(name))
+    }
+}
+
+/// Normalized names of the dependencies a Python manifest declares, including
+/// optional and development dependencies. Returns `None` for files that are
+/// not Python manifests or cannot be parsed.
+///
+/// Files included by a requirements file are not followed; use
+/// [`requirements_with_includes`] for that.
+pub fn dependencies(file_name: &str, content: &str) -> Option<Vec<String>> {
+    let mut names = match file_name {
+        "pyproject.toml" => pyproject_dependencies(&content.parse().ok()?),
+        "Pipfile" => pipfile_dependencies(&content.parse().ok()?),
+        "setup.cfg" => setup_cfg_dependencies(content),
+        _ if is_requirements_name(file_name) => parse_requirements(content).names,
+        _ => return None,
+    };
+    names.sort();
+    names.dedup();
+    Some(names)
+}
+
+/// Checks whether a Python manifest declares the given dependency
+pub fn declares_dependency(file_name: &str, content: &str, name: &str) -> bool {
+    dependencies(file_name, content).is_some_and(|deps| deps.iter().any(|d| d == name))
+}
+
+/// A console script a package installs, calling `function` in `module`
+#[derive(Debug, Clone)]
+pub struct EntryPoint {
+    pub name: String,
+    pub module: String,
+    /// Attribute path of the callable within the module, e.g. `cli.main`
+    pub function: String,
+}
+
+impl EntryPoint {
+    /// Parses an entry point reference like `pkg.cli:main [extra]`
+    fn parse(name: &str, reference: &str) -> Option<Self> {
+        let reference = reference.split('[').next()?.trim();
+        let (module, function) = reference.split_once(':')?;
+        let is_path = |text: &str| {
+            !text.is_empty()
+                && text.split('.').all(|part| {
+                    part.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                        && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+                })
+        };
+        let (module, function) = (module.trim(), function.trim());
+        if name.is_empty() || !is_path(module) || !is_path(function) {
+            return None;
+        }
+        Some(EntryPoint {
+            name: name.to_string(),
+            module: module.to_string(),
+            function: function.to_string(),
+        })
+    }
+
+    /// Python source that runs the script the way its installed wrapper
+    /// does: the callable's return value becomes the exit status
+    pub fn launch_code(&self) -> String {
+        let (object, _) = self
+            .function
+            .split_once('.')
+            .unwrap_or((&self.function, ""));
+        format!(
+            "import sys; from {} import {}; sys.argv[0] = {:?}; sys.exit({}())",
+            self.module, object, self.name, self.function
+        )
+    }
+}
+
+/// Console scripts declared in a Python manifest: `[project.scripts]` and
+/// `[tool.poetry.scripts]` of pyproject.toml, or `console_scripts` in the
+/// `[options.entry_points]` of setup.cfg
+pub fn entry_points(file_name: &str, content: &str) -> Vec<EntryPoint> {
+    match file_name {
+        "pyproject.toml" => content
+            .parse::<Table>()
+            .map(|document| pyproject_entry_points(&document))
+            .unwrap_or_default(),
+        "setup.cfg" => setup_cfg_entry_points(content),
+        _ => Vec::new(),
+    }
+}
+
+fn pyproject_entry_points(document: &Table) -> Vec<EntryPoint> {
+    let scripts = [
+        document.get("project").and_then(|p| p.get("scripts")),
+        document
+            .get("tool")
+            .and_then(|t| t.get("poetry"))
+            .and_then(|p| p.get("scripts")),
+    ];
+    scripts
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_table)
+        .flatten()
+        .filter_map(|(name, reference)| {
+            // Poetry also accepts `{ callable = "..." }` and
+            // `{ reference = "...", type = "console" }`
+            let reference = match reference {
+                Value::String(reference) => reference.as_str(),
+                Value::Table(table) => table
+                    .get("callable")
+                    .or_else(|| table.get("reference"))
+                    .and_then(Value::as_str)?,
+                _ => return None,
+            };
+            EntryPoint::parse(name, reference)
+        })
+        .collect()
+}
+
+fn setup_cfg_entry_points(content: &str) -> Vec<EntryPoint> {
+    let mut entry_points = Vec::new();
+    let mut section = String::new();
+    let mut in_scripts = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            in_scripts = false;
+            continue;
+        }
+
+        // Scripts are `name = module:function` lines in the value of the
+        // `console_scripts` group
+        let entry = if line.starts_with(char::is_whitespace) {
+            if !in_scripts {
+                continue;
+            }
+            trimmed
+        } else {
+            let Some((key, value)) = trimmed.split_once(['=', ':']) else {
+                continue;
+            };
+            in_scripts = section == "options.entry_points" && key.trim() == "console_scripts";
+            if !in_scripts {
+                continue;
+            }
+            value.trim()
+        };
+        if let Some((name, reference)) = entry.split_once('=') {
+            entry_points.extend(EntryPoint::parse(name.trim(), reference));
+        }
+    }
+    entry_points
+}
+
+/// Requirement names in a list of PEP 508 strings
+fn requirement_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(requirement_name)
+        .collect()
+}
+
+/// Requirement names in a table of PEP 508 string lists, as used for extras
+/// and dependency groups
+fn requirement_lists(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|table| table.values())
+        .flat_map(|list| requirement_list(Some(list)))
+        .collect()
+}
+
+/// Keys of a table of dependencies, as used by Poetry and Pipenv
+fn dependency_keys(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|table| table.keys())
+        .filter(|name| *name != "python")
+        .map(|name| normalize_name(name))
+        .collect()
+}
+
+/// Dependencies from PEP 621 metadata, PEP 735 dependency groups and the
+/// tool tables of Poetry, PDM, Hatch and uv
+fn pyproject_dependencies(document: &Table) -> Vec<String> {
+    let tool = |path: &[&str]| {
+        path.iter()
+            .try_fold(document.get("tool")?, |value, key| value.get(key))
+    };
+
+    let mut names = Vec::new();
+    if let Some(project) = document.get("project") {
+        names.extend(requirement_list(project.get("dependencies")));
+        names.extend(requirement_lists(project.get("optional-dependencies")));
+    }
+    names.extend(requirement_lists(document.get("dependency-groups")));
+
+    names.extend(dependency_keys(tool(&["poetry", "dependencies"])));
+    names.extend(dependency_keys(tool(&["poetry", "dev-dependencies"])));
+    if let Some(groups) = tool(&["poetry", "group"]).and_then(Value::as_table) {
+        for group in groups.values() {
+            names.extend(dependency_keys(group.get("dependencies")));
+        }
+    }
+
+    names.extend(requirement_lists(tool(&["pdm", "dev-dependencies"])));
+    if let Some(envs) = tool(&["hatch", "envs"]).and_then(Value::as_table) {
+        for env in envs.values() {
+            names.extend(requirement_list(env.get("dependencies")));
+            names.extend(requirement_list(env.get("extra-dependencies")));
+        }
+    }
+    names.extend(requirement_list(tool(&["uv", "dev-dependencies"])));
+    names
+}
+
+fn pipfile_dependencies(document: &Table) -> Vec<String> {
+    let mut names = dependency_keys(document.get("packages"));
+    names.extend(dependency_keys(document.get("dev-packages")));
+    names
+}
+
+/// Dependencies from `install_requires`, `tests_require` and
+/// `[options.extras_require]` of a setuptools setup.cfg
+fn setup_cfg_dependencies(content: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut section = String::new();
+    let mut in_requirements = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            in_requirements = false;
+            continue;
+        }
+
+        // Indented lines continue the value of the previous key
+        let value = if line.starts_with(char::is_whitespace) {
+            if !in_requirements {
+                continue;
+            }
+            trimmed
+        } else {
+            let Some((key, value)) = trimmed.split_once(['=', ':']) else {
+                continue;
+            };
+            in_requirements = match section.as_str() {
+                "options" => matches!(key.trim(), "install_requires" | "tests_require"),
+                "options.extras_require" => true,
+                _ => false,
+            };
+            if !in_requirements {
+                continue;
+            }
+            value.trim()
+        };
+        names.extend(requirement_name(value));
+    }
+    names
+}
+
+/// Whether a file name follows the `requirements*.txt` convention
+fn is_requirements_name(file_name: &str) -> bool {
+    file_name.starts_with("requirements") && file_name.ends_with(".txt")
+}
+
+/// Whether a path is a pip requirements file: `requirements*.txt`, or any
+/// `.txt` file in a `requirements` directory
+pub fn is_requirements_file(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    is_requirements_name(&name)
+        || (name.ends_with(".txt")
+            && path
+                .parent()
+                .and_then(Path::file_name)
+                .is_some_and(|dir| dir == "requirements"))
+}
+
+/// The contents of a requirements file
+#[derive(Debug, Default)]
+pub struct Requirements {
+    /// Normalized names of the required distributions
+    pub names: Vec<String>,
+    /// Files named by `-r`/`--requirement`, relative to the including file
+    pub includes: Vec<String>,
+}
+
+/// Parses a pip requirements file. Extras and environment markers are
+/// dropped from the names; comments, options other than `-r`/`-e`, and
+/// requirements without a name (plain paths or URLs without `#egg=`) are
+/// skipped.
+pub fn parse_requirements(content: &str) -> Requirements {
+    let mut parsed = Requirements::default();
+    let mut logical = String::new();
+    for line in content.lines() {
+        // A trailing backslash joins the next line
+        if let Some(start) = line.strip_suffix('\\') {
+            logical.push_str(start);
+            continue;
+        }
+        logical.push_str(line);
+        let line = std::mem::take(&mut logical);
+        parse_requirement_line(strip_comment(&line).trim(), &mut parsed);
+    }
+    parse_requirement_line(strip_comment(&logical).trim(), &mut parsed);
+    parsed
+}
+
+/// Removes a `#` comment, which pip only recognizes at the start of a line
+/// or after whitespace
+fn strip_comment(line: &str) -> &str {
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        if c == '#' && previous.is_whitespace() {
+            return &line[..index];
+        }
+        previous = c;
+    }
+    line
+}
+
+fn parse_requirement_line(line: &str, parsed: &mut Requirements) {
+    if line.is_empty() {
+        return;
+    }
+    if line.starts_with('-') {
+        let (option, value) = match line.split_once(['=', ' ', '\t']) {
+            Some((option, value)) => (option, value.trim()),
+            None if line.starts_with("-r") || line.starts_with("-e") => line.split_at(2),
+            None => (line, ""),
+        };
+        match option {
+            "-r" | "--requirement" => parsed.includes.push(value.to_string()),
+            "-e" | "--editable" => parsed.names.extend(egg_name(value)),
+            _ => {}
+        }
+        return;
+    }
+    if line.contains("://") && !line.contains(" @ ") {
+        parsed.names.extend(egg_name(line));
+        return;
+    }
+    if line.starts_with(['.', '/']) {
+        return;
+    }
+
+    // Extras and markers follow the name, which is all detection needs
+    parsed.names.extend(requirement_name(line));
+}
+
+/// Reads the name from the `#egg=` fragment of a URL or editable path
+fn egg_name(url: &str) -> Option<String> {
+    requirement_name(url.split_once("#egg=")?.1)
+}
+
+/// Parses a requirements file and every file it includes, directly or
+/// indirectly. Returns each requirement's name with the file declaring it.
+///
+/// `read` returns the content of an included file, or `None` if it cannot
+/// be read; such includes are skipped.
+pub fn requirements_with_includes(
+    path: &Path,
+    content: &str,
+    read: impl Fn(&Path) -> Option<String>,
+) -> Vec<(PathBuf, String)> {
+    let mut found = Vec::new();
+    let path = normalize_path(path);
+    let mut seen = HashSet::from([path.clone()]);
+    let mut pending = vec![(path, content.to_string())];
+    while let Some((path, content)) = pending.pop() {
+        let parsed = parse_requirements(&content);
+        for include in parsed.includes {
+            let included = normalize_path(&path.parent().unwrap_or(Path::new("")).join(include));
+            if seen.insert(included.clone())
+                && let Some(content) = read(&included)
+            {
+                pending.push((included, content));
+            }
+        }
+        found.extend(parsed.names.into_iter().map(|name| (path.clone(), name)));
+    }
+    found
+}
+
+/// Resolves `.` and `..` components without touching the file system, so
+/// that include cycles are recognized
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Directories of project-local virtual environments, as created by
+/// `python -m venv`, uv and Poetry with `virtualenvs.in-project`
+const LOCAL_ENVS: &[&str] = &[".venv", "venv"];
+
+/// Finds the interpreter the project's dependencies are installed for.
+///
+/// Looks, in order, for a virtual environment in the project root, the
+/// interpreter PDM selected in `.pdm-python` and the environment uv is
+/// pointed at with `UV_PROJECT_ENVIRONMENT`. Only with `external` does it go
+/// on to a Poetry environment in Poetry's cache, the conda environment named
+/// in `environment.yml` and the pyenv versions listed in `.python-version`,
+/// and accept PDM and uv interpreters outside the project; such paths are
+/// specific to this machine. Interpreters inside the project are given
+/// relative to `${workspaceFolder}`.
+pub fn find_interpreter(root: &Path, pyproject: Option<&str>, external: bool) -> Option<String> {
+    let display = |python: PathBuf| match python.strip_prefix(root) {
+        Ok(relative) => Some(format!(
+            "${{workspaceFolder}}/{}",
+            relative.to_string_lossy().replace('\\', "/")
+        )),
+        Err(_) if external => Some(python.to_string_lossy().into_owned()),
+        Err(_) => None,
+    };
+
+    let local = LOCAL_ENVS
+        .iter()
+        .find_map(|dir| env_interpreter(&root.join(dir)))
+        .or_else(|| pdm_interpreter(root))
+        .or_else(|| uv_interpreter(root))
+        .and_then(display);
+    if local.is_some() || !external {
+        return local;
+    }
+    pyproject
+        .and_then(|pyproject| poetry_interpreter(root, pyproject))
+        .or_else(|| conda_interpreter(root))
+        .or_else(|| pyenv_interpreter(root))
+        .and_then(display)
+}
+
+/// The interpreter of an environment, in the Unix or the Windows layout
+fn env_interpreter(env: &Path) -> Option<PathBuf> {
+    ["bin/python", "Scripts/python.exe", "python.exe"]
+        .iter()
+        .map(|python| env.join(python))
+        .find(|python| python.is_file())
+}
+
+fn pdm_interpreter(root: &Path) -> Option<PathBuf> {
+    let content = fs::read_to_string(root.join(".pdm-python")).ok()?;
+    let python = root.join(content.trim());
+    python.is_file().then_some(python)
+}
+
+fn uv_interpreter(root: &Path) -> Option<PathBuf> {
+    if !root.join("uv.lock").is_file() {
+        return None;
+    }
+    let env = env::var_os("UV_PROJECT_ENVIRONMENT")?;
+    env_interpreter(&root.join(env))
+}
+
+/// Poetry names environments `<name>-<hash of the project path>-py<version>`;
+/// without computing the hash, an environment is only picked when it is the
+/// only one for the project name
+fn poetry_interpreter(root: &Path, pyproject: &str) -> Option<PathBuf> {
+    let document = pyproject.parse::<Table>().ok()?;
+    let poetry = document.get("tool").and_then(|tool| tool.get("poetry"));
+    if poetry.is_none() && !root.join("poetry.lock").is_file() {
+        return None;
+    }
+    let name = poetry
+        .and_then(|poetry| poetry.get("name"))
+        .or_else(|| document.get("project")?.get("name"))
+        .and_then(Value::as_str)?;
+
+    let envs_dir = match env::var_os("POETRY_VIRTUALENVS_PATH") {
+        Some(dir) => PathBuf::from(dir),
+        None if cfg!(windows) => dirs::cache_dir()?.join("pypoetry/Cache/virtualenvs"),
+        None => dirs::cache_dir()?.join("pypoetry/virtualenvs"),
+    };
+    let prefix = format!("{}-", normalize_name(name));
+    let mut envs = fs::read_dir(envs_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.get(8..))
+                .is_some_and(|rest| rest.starts_with("-py"))
+        })
+        .filter_map(|entry| env_interpreter(&entry.path()));
+    let python = envs.next()?;
+    envs.next().is_none().then_some(python)
+}
+
+/// The environment named by the `name` or `prefix` key of a conda
+/// environment file, looked up in the usual conda installation directories
+fn conda_interpreter(root: &Path) -> Option<PathBuf> {
+    let content = ["environment.yml", "environment.yaml"]
+        .iter()
+        .find_map(|file| fs::read_to_string(root.join(file)).ok())?;
+    let value = |key: &str| {
+        content.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix(':')?;
+            Some(value.trim().trim_matches(['"', '\'']).to_string())
+        })
+    };
+    if let Some(prefix) = value("prefix") {
+        return env_interpreter(Path::new(&prefix));
+    }
+
+    let name = value("name")?;
+    let home = dirs::home_dir()?;
+    let mut envs_dirs: Vec<PathBuf> = env::var_os("CONDA_EXE")
+        .and_then(|exe| Some(Path::new(&exe).parent()?.parent()?.join("envs")))
+        .into_iter()
+        .collect();
+    envs_dirs.extend(
+        [
+            ".conda",
+            "miniconda3",
+            "anaconda3",
+            "miniforge3",
+            "mambaforge",
+        ]
+        .iter()
+        .map(|dir| home.join(dir).join("envs")),
+    );
+    envs_dirs
+        .iter()
+        .find_map(|dir| env_interpreter(&dir.join(&name)))
+}
+
+/// The first version from `.python-version` that pyenv has installed
+fn pyenv_interpreter(root: &Path) -> Option<PathBuf> {
+    let content = fs::read_to_string(root.join(".python-version")).ok()?;
+    let pyenv_root = match env::var_os("PYENV_ROOT") {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::home_dir()?.join(".pyenv"),
+    };
+    content
+        .lines()
+        .map(|line| strip_comment(line).trim())
+        .filter(|version| !version.is_empty())
+        .find_map(|version| env_interpreter(&pyenv_root.join("versions").join(version)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn scripts(entry_points: &[EntryPoint]) -> Vec<(&str, &str, &str)> {
+        entry_points
+            .iter()
+            .map(|e| (e.name.as_str(), e.module.as_str(), e.function.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn entry_point_references_drop_extras() {
+        let entry_point = EntryPoint::parse("tool", " pkg.cli : main [extra]").unwrap();
+        assert_eq!(scripts(&[entry_point]), [("tool", "pkg.cli", "main")]);
+    }
+
+    #[test]
+    fn invalid_entry_point_references_are_rejected() {
+        for reference in [
+            "pkg.cli",
+            "pkg:",
+            ":main",
+            "pkg-cli:main",
+            "pkg:main()",
+            "1pkg:main",
+        ] {
+            assert!(
+                EntryPoint::parse("tool", reference).is_none(),
+                "{}",
+                reference
+            );
+        }
+        assert!(EntryPoint::parse("", "pkg:main").is_none());
+    }
+
+    #[test]
+    fn launch_code_imports_the_object_holding_the_callable() {
+        let function = EntryPoint::parse("tool", "pkg.cli:main").unwrap();
+        assert_eq!(
+            function.launch_code(),
+            "import sys; from pkg.cli import main; sys.argv[0] = \"tool\"; sys.exit(main())"
+        );
+        let method = EntryPoint::parse("tool", "pkg.cli:App.run").unwrap();
+        assert_eq!(
+            method.launch_code(),
+            "import sys; from pkg.cli import App; sys.argv[0] = \"tool\"; sys.exit(App.run())"
+        );
+    }
+
+    #[test]
+    fn pyproject_scripts_include_poetry_tables() {
+        let content = r#"
+            [project.scripts]
+            pep621 = "pkg.cli:main"
+
+            [tool.poetry.scripts]
+            plain = "pkg.plain:main"
+            callable = { callable = "pkg.callable:main" }
+            reference = { reference = "pkg.reference:main", type = "console" }
+            file = { reference = "bin/run.sh", type = "file" }
+        "#;
+        assert_eq!(
+            scripts(&entry_points("pyproject.toml", content)),
+            [
+                ("pep621", "pkg.cli", "main"),
+                ("callable", "pkg.callable", "main"),
+                ("plain", "pkg.plain", "main"),
+                ("reference", "pkg.reference", "main"),
+            ]
+        );
+    }
+
+    #[test]
+    fn setup_cfg_console_scripts_on_the_same_and_following_lines() {
+        let same_line = "[options.entry_points]\nconsole_scripts = tool = pkg.cli:main\n";
+        assert_eq!(
+            scripts(&entry_points("setup.cfg", same_line)),
+            [("tool", "pkg.cli", "main")]
+        );
+
+        let continued = "[options.entry_points]\n\
+                         console_scripts =\n    \
+                             first = pkg.a:main\n    \
+                             # comment\n    \
+                             second = pkg.b:Cli.run\n\
+                         gui_scripts =\n    \
+                             gui = pkg.gui:main\n\
+                         [options]\n\
+                         console_scripts = other = pkg.c:main\n";
+        assert_eq!(
+            scripts(&entry_points("setup.cfg", continued)),
+            [("first", "pkg.a", "main"), ("second", "pkg.b", "Cli.run")]
+        );
+    }
+
+    fn sorted(mut names: Vec<String>) -> Vec<String> {
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn pyproject_dependencies_cover_standard_and_tool_tables() {
+        let content = r#"
+            [project]
+            dependencies = ["Flask[async]>=2.0", "requests; python_version >= '3.8'"]
+            [project.optional-dependencies]
+            test = ["pytest"]
+
+            [dependency-groups]
+            lint = ["ruff"]
+
+            [tool.poetry.dependencies]
+            python = "^3.10"
+            Django = "^5.0"
+            [tool.poetry.dev-dependencies]
+            black = "*"
+            [tool.poetry.group.docs.dependencies]
+            mkdocs = "*"
+
+            [tool.pdm.dev-dependencies]
+            dev = ["mypy"]
+            [tool.hatch.envs.default]
+            dependencies = ["coverage"]
+            extra-dependencies = ["hypothesis"]
+            [tool.uv]
+            dev-dependencies = ["fastapi"]
+        "#;
+        assert_eq!(
+            sorted(dependencies("pyproject.toml", content).unwrap()),
+            [
+                "black",
+                "coverage",
+                "django",
+                "fastapi",
+                "flask",
+                "hypothesis",
+                "mkdocs",
+                "mypy",
+                "pytest",
+                "requests",
+                "ruff",
+            ]
+        );
+    }
+
+    #[test]
+    fn pipfile_dependencies_include_dev_packages() {
+        let content = r#"
+            [packages]
+            Flask = "*"
+            [dev-packages]
+            pytest = { version = "*" }
+            [requires]
+            python_version = "3.12"
+        "#;
+        assert_eq!(
+            sorted(dependencies("Pipfile", content).unwrap()),
+            ["flask", "pytest"]
+        );
+    }
+
+    #[test]
+    fn setup_cfg_dependencies_follow_continuation_lines() {
+        let content = "[metadata]\n\
+                       name = not_a_dependency\n\
+                       [options]\n\
+                       install_requires = flask>=2\n    \
+                           requests\n    \
+                           # comment\n\
+                       python_requires = >=3.8\n\
+                       tests_require =\n    \
+                           pytest\n\
+                       [options.extras_require]\n\
+                       web = fastapi\n    \
+                           Django\n";
+        assert_eq!(
+            sorted(dependencies("setup.cfg", content).unwrap()),
+            ["django", "fastapi", "flask", "pytest", "requests"]
+        );
+    }
+
+    #[test]
+    fn dependencies_need_a_python_manifest() {
+        assert!(dependencies("package.json", "{}").is_none());
+        assert!(dependencies("pyproject.toml", "not [toml").is_none());
+        assert!(declares_dependency(
+            "Pipfile",
+            "[packages]\nflask = \"*\"\n",
+            "flask"
+        ));
+        assert!(!declares_dependency(
+            "Pipfile",
+            "[packages]\nflask = \"*\"\n",
+            "django"
+        ));
+    }
+
+    #[test]
+    fn requirements_drop_extras_markers_and_versions() {
+        let parsed = parse_requirements(
+            "Flask[async]>=2.0\n\
+             Django_REST.framework==3.14 ; python_version >= \"3.8\"\n\
+             requests @ https://example.com/requests.zip\n\
+             uvicorn\\\n[standard]\n",
+        );
+        assert_eq!(
+            parsed.names,
+            ["flask", "django-rest-framework", "requests", "uvicorn"]
+        );
+    }
+
+    #[test]
+    fn requirements_follow_comment_rules() {
+        let parsed = parse_requirements(
+            "# flask\n\
+             pytest # a comment\n\
+             \t# indented comment\n\
+             https://example.com/kept.zip#egg=kept # a comment\n",
+        );
+        assert_eq!(parsed.names, ["pytest", "kept"]);
+    }
+
+    #[test]
+    fn requirements_read_egg_fragments() {
+        let parsed = parse_requirements(
+            "-e git+https://example.com/repo.git#egg=My_Package\n\
+             --editable=./local#egg=other\n\
+             -e ./without-egg\n\
+             https://example.com/archive.zip#egg=archived\n\
+             https://example.com/unnamed.zip\n\
+             ./local/path\n",
+        );
+        assert_eq!(parsed.names, ["my-package", "other", "archived"]);
+    }
+
+    #[test]
+    fn requirements_collect_includes_and_skip_other_options() {
+        let parsed = parse_requirements(
+            "-r base.txt\n\
+             --requirement=dev.txt\n\
+             -rextra.txt\n\
+             -c constraints.txt\n\
+             --index-url https://example.com/simple\n\
+             flask\n",
+        );
+        assert_eq!(parsed.includes, ["base.txt", "dev.txt", "extra.txt"]);
+        assert_eq!(parsed.names, ["flask"]);
+    }
+
+    #[test]
+    fn includes_are_followed_once() {
+        let files = HashMap::from([
+            (
+                PathBuf::from("requirements/base.txt"),
+                "-r ../requirements.txt\ndjango\n",
+            ),
+            (
+                PathBuf::from("requirements/dev.txt"),
+                "-r ./base.txt\npytest\n",
+            ),
+        ]);
+        let found = requirements_with_includes(
+            Path::new("requirements.txt"),
+            "-r requirements/dev.txt\n-r requirements/missing.txt\nflask\n",
+            |path| files.get(path).map(|content| content.to_string()),
+        );
+        let mut found: Vec<(String, String)> = found
+            .into_iter()
+            .map(|(path, name)| (path.to_string_lossy().replace('\\', "/"), name))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("requirements.txt".to_string(), "flask".to_string()),
+                ("requirements/base.txt".to_string(), "django".to_string()),
+                ("requirements/dev.txt".to_string(), "pytest".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn requirements_files_are_recognized_by_path() {
+        assert!(is_requirements_file(Path::new("requirements.txt")));
+        assert!(is_requirements_file(Path::new("requirements-dev.txt")));
+        assert!(is_requirements_file(Path::new("requirements/test.txt")));
+        assert!(!is_requirements_file(Path::new("notes.txt")));
+        assert!(!is_requirements_file(Path::new("requirements.in")));
+    }
+}
@@ -0,0 +1,117 @@
+use std::error::Error;
+
+use serde_json::{Value, json};
+
+use crate::jsonc;
+
+/// Returns the tasks.json definition for a `preLaunchTask` label emitted by
+/// one of the providers
+pub fn task_definition(label: &str) -> Option<Value> {
+    let task = match label {
+        "cargo build" => json!({
+            "label": label,
+            "type": "shell",
+            "command": "cargo",
+            "args": ["build"],
+            "problemMatcher": ["$rustc"],
+            "group": "build"
+        }),
+        "tsc: build - tsconfig.json" => json!({
+            "label": label,
+            "type": "typescript",
+            "tsconfig": "tsconfig.json",
+            "problemMatcher": ["$tsc"],
+            "group": "build"
+        }),
+        "C/C++: Build active file" => json!({
+            "label": label,
+            "type": "cppbuild",
+            "command": "g++",
+            "args": [
+                "-fdiagnostics-color=always",
+                "-g",
+                "${file}",
+                "-o",
+                // Next to the source, as g++ does not create missing directories
+                "${fileDirname}/${fileBasenameNoExtension}"
+            ],
+            "options": {
+                "cwd": "${workspaceFolder}"
+            },
+            "problemMatcher": ["$gcc"],
+            "group": "build"
+        }),
+        _ => return None,
+    };
+    Some(task)
+}
+
+/// Collects the `preLaunchTask` labels referenced by the configurations, in
+/// order of first use
+pub fn referenced_labels(configs: &[Value]) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for config in configs {
+        if let Some(label) = config.get("preLaunchTask").and_then(Value::as_str)
+            && !labels.iter().any(|l| l == label)
+        {
+            labels.push(label.to_string());
+        }
+    }
+    labels
+}
+
+fn task_label(task: &Value) -> Option<&str> {
+    task.get("label").and_then(Value::as_str)
+}
+
+/// Adds the tasks whose labels are missing to an existing tasks.json.
+///
+/// Tasks already present, whatever their definition, are left alone, as are
+/// comments and formatting of the rest of the document.
+pub fn merge_tasks_document(text: &str, tasks: &[Value]) -> Result<String, Box<dyn Error>> {
+    let mut document = jsonc::parse(text)?;
+    let existing = document
+        .get("tasks")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let missing: Vec<Value> = tasks
+        .iter()
+        .filter(|task| !existing.iter().any(|e| task_label(e) == task_label(task)))
+        .cloned()
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(text.to_string());
+    }
+    if let Some(merged) = jsonc::rewrite_array(text, "tasks", &[], &missing) {
+        return Ok(merged);
+    }
+
+    // No tasks array to edit in place, so fall back to re-serializing
+    let mut merged = existing;
+    merged.extend(missing);
+    match document.as_object_mut() {
+        Some(map) => {
+            map.insert("tasks".to_string(), json!(merged));
+        }
+        None => {
+            document = json!({
+                "version": "2.0.0",
+                "tasks": merged
+            });
+        }
+    }
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Lists the labels defined in the text of a tasks.json
+pub fn defined_labels(text: &str) -> Vec<String> {
+    jsonc::parse(text)
+        .ok()
+        .and_then(|document| document.get("tasks").and_then(Value::as_array).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|task| task_label(task).map(str::to_string))
+        .collect()
+}
@@ -0,0 +1,314 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::launch::LaunchConfig;
+use crate::project::Project;
+use crate::types::{ConfigProvider, Params};
+
+/// Directory holding template provider files, below the project root and
+/// below the user's config directory
+pub const PROVIDERS_DIR: &str = ".vscode-launch-gen/providers";
+
+/// Placeholder set by a parameter given without a key
+const PARAM: &str = "param";
+
+/// A template provider file, written in TOML or JSON
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateFile {
+    name: String,
+    #[serde(default)]
+    detect: DetectRules,
+    /// The `{{placeholder}}`s used in the templates with their default
+    /// values; these are the parameters the provider accepts
+    #[serde(default)]
+    params: BTreeMap<String, String>,
+    /// launch.json entries with `{{placeholder}}`s in their string values
+    configurations: Vec<Value>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DetectRules {
+    /// Glob patterns matched against paths relative to the project root, or
+    /// against the file name for patterns without a `/`
+    files: Vec<String>,
+    content: Vec<ContentRule>,
+}
+
+/// Matches a file with this name that contains the given text
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContentRule {
+    file: String,
+    contains: String,
+}
+
+/// A provider declared in a template file instead of in code
+pub struct TemplateProvider {
+    name: String,
+    root: PathBuf,
+    files: Vec<glob::Pattern>,
+    content: Vec<ContentRule>,
+    params: BTreeMap<String, String>,
+    configurations: Vec<Value>,
+}
+
+impl TemplateProvider {
+    fn from_file(path: &Path, root: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        let file: TemplateFile = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content)?
+        } else {
+            toml::from_str(&content)?
+        };
+        let files = file
+            .detect
+            .files
+            .iter()
+            .map(|pattern| glob::Pattern::new(pattern))
+            .collect::<Result<_, _>>()?;
+
+        Ok(TemplateProvider {
+            name: file.name,
+            root: root.to_path_buf(),
+            files,
+            content: file.detect.content,
+            params: file.params,
+            configurations: file.configurations,
+        })
+    }
+
+    /// Replaces every `{{placeholder}}` in the string values of `value`
+    fn expand(
+        value: &Value,
+        params: &BTreeMap<String, String>,
+    ) -> Result<Value, Box<dyn std::error::Error>> {
+        Ok(match value {
+            Value::String(text) => Value::String(expand_str(text, params)?),
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| Self::expand(item, params))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, item)| Ok((key.clone(), Self::expand(item, params)?)))
+                    .collect::<Result<_, Box<dyn std::error::Error>>>()?,
+            ),
+            other => other.clone(),
+        })
+    }
+}
+
+fn expand_str(
+    text: &str,
+    params: &BTreeMap<String, String>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut expanded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + len].trim();
+        let value = params
+            .get(key)
+            .ok_or_else(|| format!("no value for placeholder '{{{{{}}}}}'", key))?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(value);
+        rest = &rest[start + len + 2..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+impl ConfigProvider for TemplateProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn param_keys(&self) -> Vec<&str> {
+        // `param` comes first so that it takes a value given without a key
+        let mut keys: Vec<&str> = self.params.keys().map(String::as_str).collect();
+        keys.sort_by_key(|key| *key != PARAM);
+        keys
+    }
+
+    fn get_configs(&self, _project: &Project, params: &Params) -> Vec<LaunchConfig> {
+        let mut values = self.params.clone();
+        for (key, value) in params.iter() {
+            values.insert(key.to_string(), value.to_string());
+        }
+
+        let mut configs = Vec::new();
+        for template in &self.configurations {
+            let config = Self::expand(template, &values)
+                .and_then(|value| Ok(LaunchConfig::from_value(value)?));
+            match config {
+                Ok(config) => configs.push(config),
+                Err(err) => eprintln!(
+                    "Warning: Skipping a configuration of provider '{}': {}",
+                    self.name, err
+                ),
+            }
+        }
+        configs
+    }
+
+    fn can_detect_from_file(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        self.files.iter().any(|pattern| {
+            if pattern.as_str().contains('/') {
+                pattern.matches_path(relative)
+            } else {
+                path.file_name()
+                    .is_some_and(|name| pattern.matches(&name.to_string_lossy()))
+            }
+        })
+    }
+
+    fn can_detect_from_content(&self, filename: &str, content: &str) -> bool {
+        self.content
+            .iter()
+            .any(|rule| rule.file == filename && content.contains(&rule.contains))
+    }
+
+    fn manifest_files(&self) -> Vec<String> {
+        self.content.iter().map(|rule| rule.file.clone()).collect()
+    }
+}
+
+/// The user's config directory: `$XDG_CONFIG_HOME` if set, else `~/.config`
+/// on every platform, rather than the platform's config directory, so that
+/// the documented location holds on macOS and Windows
+fn user_config_dir() -> Option<PathBuf> {
+    match env::var_os("XDG_CONFIG_HOME").map(PathBuf::from) {
+        Some(dir) if dir.is_absolute() => Some(dir),
+        _ => dirs::home_dir().map(|home| home.join(".config")),
+    }
+}
+
+/// Loads the template providers from `vscode-launch-gen/providers` in the
+/// user's config directory and from the project, in that order. Files that
+/// cannot be read are reported and skipped.
+pub fn load_providers(root: &Path) -> Vec<TemplateProvider> {
+    let mut dirs: Vec<PathBuf> = user_config_dir()
+        .map(|dir| dir.join("vscode-launch-gen/providers"))
+        .into_iter()
+        .collect();
+    dirs.push(root.join(PROVIDERS_DIR));
+
+    let mut providers = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == "toml" || ext == "json")
+            })
+            .collect();
+        paths.sort();
+        for path in paths {
+            match TemplateProvider::from_file(&path, root) {
+                Ok(provider) => providers.push(provider),
+                Err(err) => eprintln!(
+                    "Warning: Ignoring provider file {}: {}",
+                    path.display(),
+                    err
+                ),
+            }
+        }
+    }
+    providers
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn provider(params: &[(&str, &str)]) -> TemplateProvider {
+        TemplateProvider {
+            name: "test".to_string(),
+            root: PathBuf::new(),
+            files: Vec::new(),
+            content: Vec::new(),
+            params: values(params),
+            configurations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn placeholders_are_replaced() {
+        let params = values(&[("port", "9229"), ("host", "localhost")]);
+        assert_eq!(
+            expand_str("{{host}}:{{ port }}/{{port}}", &params).unwrap(),
+            "localhost:9229/9229"
+        );
+        assert_eq!(
+            expand_str("no placeholders", &params).unwrap(),
+            "no placeholders"
+        );
+        assert_eq!(expand_str("open {{port", &params).unwrap(), "open {{port");
+    }
+
+    #[test]
+    fn missing_placeholder_is_an_error() {
+        let err = expand_str("{{missing}}", &values(&[])).unwrap_err();
+        assert_eq!(err.to_string(), "no value for placeholder '{{missing}}'");
+    }
+
+    #[test]
+    fn expansion_reaches_nested_strings_only() {
+        let template = json!({
+            "name": "Attach {{port}}",
+            "port": 1,
+            "args": ["--port", "{{port}}"],
+            "connect": { "port": "{{port}}" }
+        });
+        let expanded = TemplateProvider::expand(&template, &values(&[("port", "9229")])).unwrap();
+        assert_eq!(
+            expanded,
+            json!({
+                "name": "Attach 9229",
+                "port": 1,
+                "args": ["--port", "9229"],
+                "connect": { "port": "9229" }
+            })
+        );
+    }
+
+    #[test]
+    fn param_comes_first_to_take_a_keyless_value() {
+        let provider = provider(&[("args", ""), ("param", "app"), ("cwd", ".")]);
+        assert_eq!(provider.param_keys(), ["param", "args", "cwd"]);
+
+        let params = Params::parse("main,cwd=src", &provider.param_keys()).unwrap();
+        assert_eq!(params.get("param"), Some("main"));
+        assert_eq!(params.get("cwd"), Some("src"));
+    }
+
+    #[test]
+    fn keys_stay_sorted_without_param() {
+        assert_eq!(provider(&[("b", ""), ("a", "")]).param_keys(), ["a", "b"]);
+    }
+}
@@ -1,14 +1,28 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 
-use serde_json::Value;
+use crate::launch::LaunchConfig;
+use crate::project::Project;
 
 /// Trait for configuration providers
 pub trait ConfigProvider {
     /// Returns the name of this configuration type
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
-    /// Returns the configuration JSON for launch.json
-    fn get_config(&self, params: Option<&str>) -> Value;
+    /// Keys this configuration type accepts as parameters. A value given
+    /// without a key sets the first one.
+    fn param_keys(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Returns the configurations this provider contributes to launch.json
+    fn get_configs(&self, project: &Project, params: &Params) -> Vec<LaunchConfig>;
+
+    /// Explains why `get_configs` returned nothing, for the warning shown
+    /// when this type was requested explicitly
+    fn no_configs_reason(&self, _project: &Project) -> String {
+        "nothing in the project to configure".to_string()
+    }
 
     /// Checks if this configuration type can be detected from a given file path
     fn can_detect_from_file(&self, path: &Path) -> bool;
@@ -17,4 +31,152 @@ pub trait ConfigProvider {
     fn can_detect_from_content(&self, _filename: &str, _content: &str) -> bool {
         false // Default implementation returns false
     }
+
+    /// File names, besides the built-in manifests, whose content is read
+    /// during the scan and passed to `can_detect_from_content`
+    fn manifest_files(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Parameters of a configuration type, given after the colon in
+/// `--type name:key=value,key=value`
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    /// Parses `key=value` pairs separated by commas, checking the keys
+    /// against `keys`. A leading value without a key sets the first key;
+    /// a later part without `=` continues the previous value, so values can
+    /// contain commas.
+    pub fn parse(text: &str, keys: &[&str]) -> Result<Self, String> {
+        let mut values: BTreeMap<String, String> = BTreeMap::new();
+        let mut current: Option<String> = None;
+        for (index, part) in text.split(',').enumerate() {
+            let (key, value) = match part.split_once('=') {
+                Some((key, value)) if keys.contains(&key.trim()) => (key.trim(), value),
+                Some((key, _)) if is_key(key.trim()) => {
+                    return Err(unknown_key(key.trim(), keys));
+                }
+                _ => match &current {
+                    Some(key) => {
+                        if let Some(value) = values.get_mut(key) {
+                            value.push(',');
+                            value.push_str(part);
+                        }
+                        continue;
+                    }
+                    None if index == 0 && !part.is_empty() => match keys.first() {
+                        Some(key) => (*key, part),
+                        None => return Err("no parameters are accepted".to_string()),
+                    },
+                    None => continue,
+                },
+            };
+            if values.insert(key.to_string(), value.to_string()).is_some() {
+                return Err(format!("parameter '{}' is given more than once", key));
+            }
+            current = Some(key.to_string());
+        }
+        Ok(Params { values })
+    }
+
+    /// Value of a parameter
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// A parameter holding a command line, split at whitespace
+    pub fn list(&self, key: &str) -> Option<Vec<String>> {
+        self.get(key)
+            .map(|value| value.split_whitespace().map(String::from).collect())
+    }
+
+    /// Adds the parameters of `other` that are not set here
+    pub fn or(mut self, other: Params) -> Self {
+        for (key, value) in other.values {
+            self.values.entry(key).or_insert(value);
+        }
+        self
+    }
+
+    /// All parameters that are set
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// Whether `text` looks like a parameter name rather than part of a value
+fn is_key(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn unknown_key(key: &str, keys: &[&str]) -> String {
+    if keys.is_empty() {
+        format!("unknown parameter '{}'; no parameters are accepted", key)
+    } else {
+        format!("unknown parameter '{}'; accepted: {}", key, keys.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYS: &[&str] = &["module", "args", "cwd"];
+
+    #[test]
+    fn keyless_first_value_sets_the_first_key() {
+        let params = Params::parse("pytest,args=-x", KEYS).unwrap();
+        assert_eq!(params.get("module"), Some("pytest"));
+        assert_eq!(params.get("args"), Some("-x"));
+    }
+
+    #[test]
+    fn keyless_value_needs_accepted_keys() {
+        assert!(Params::parse("value", &[]).is_err());
+        assert!(Params::parse("", &[]).unwrap().iter().next().is_none());
+    }
+
+    #[test]
+    fn parts_without_a_key_continue_the_previous_value() {
+        let params = Params::parse("args=--ids=1,2,3,cwd=src", KEYS).unwrap();
+        assert_eq!(params.get("args"), Some("--ids=1,2,3"));
+        assert_eq!(params.get("cwd"), Some("src"));
+    }
+
+    #[test]
+    fn values_can_contain_equals_signs() {
+        let params = Params::parse("args=--level=debug x=1", KEYS).unwrap();
+        assert_eq!(params.list("args").unwrap(), ["--level=debug", "x=1"]);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let err = Params::parse("modul=pytest", KEYS).unwrap_err();
+        assert!(err.contains("unknown parameter 'modul'"));
+        assert!(err.contains("module, args, cwd"));
+        let err = Params::parse("key=value", &[]).unwrap_err();
+        assert!(err.contains("no parameters are accepted"));
+    }
+
+    #[test]
+    fn duplicate_keys_are_rejected() {
+        let err = Params::parse("pytest,module=unittest", KEYS).unwrap_err();
+        assert!(err.contains("'module' is given more than once"));
+    }
+
+    #[test]
+    fn given_parameters_take_precedence() {
+        let given = Params::parse("module=pytest", KEYS).unwrap();
+        let defaults = Params::parse("module=unittest,cwd=tests", KEYS).unwrap();
+        let params = given.or(defaults);
+        assert_eq!(params.get("module"), Some("pytest"));
+        assert_eq!(params.get("cwd"), Some("tests"));
+    }
 }
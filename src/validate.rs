@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Allowed and required launch.json properties per debugger type, based on
+/// the configuration attributes each debug adapter contributes to VS Code
+const SCHEMA_DATA: &str = include_str!("../schemas/debuggers.json");
+
+#[derive(Deserialize)]
+struct Schema {
+    /// Properties VS Code accepts for every debugger type
+    common: HashMap<String, String>,
+    /// Debugger types sharing another type's schema
+    aliases: HashMap<String, String>,
+    debuggers: HashMap<String, DebuggerSchema>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DebuggerSchema {
+    /// Properties a launch request must set
+    #[serde(default)]
+    required: Vec<String>,
+    /// Properties of which a launch request must set at least one
+    #[serde(default)]
+    any_of: Vec<String>,
+    /// Adapter-specific properties, mapped to their `|`-separated JSON kinds
+    properties: HashMap<String, String>,
+}
+
+fn schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        serde_json::from_str(SCHEMA_DATA).expect("bundled debugger schema is valid JSON")
+    })
+}
+
+fn kind_matches(kinds: &str, value: &Value) -> bool {
+    kinds.split('|').any(|kind| match kind {
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "number" => value.is_number(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    })
+}
+
+/// Checks a configuration against the schema of its debugger type and
+/// returns a description of every problem found.
+///
+/// Configurations of debugger types without bundled schema data only get
+/// the common properties checked.
+pub fn validate_config(config: &Value) -> Vec<String> {
+    let schema = schema();
+    let mut errors = Vec::new();
+
+    let Some(map) = config.as_object() else {
+        return vec!["configuration is not a JSON object".to_string()];
+    };
+    let name = map
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("<unnamed>");
+    let mut error = |message: String| errors.push(format!("'{}': {}", name, message));
+
+    for key in ["name", "type", "request"] {
+        if !map.contains_key(key) {
+            error(format!("missing required property '{}'", key));
+        }
+    }
+    let request = map.get("request").and_then(Value::as_str);
+    if let Some(request) = request
+        && request != "launch"
+        && request != "attach"
+    {
+        error(format!(
+            "'request' must be \"launch\" or \"attach\", not \"{}\"",
+            request
+        ));
+    }
+
+    let type_name = map.get("type").and_then(Value::as_str).unwrap_or_default();
+    let type_name = schema
+        .aliases
+        .get(type_name)
+        .map_or(type_name, String::as_str);
+    let debugger = schema.debuggers.get(type_name);
+
+    for (key, value) in map {
+        let kinds = schema
+            .common
+            .get(key)
+            .or_else(|| debugger.and_then(|d| d.properties.get(key)));
+        match kinds {
+            Some(kinds) if !kind_matches(kinds, value) => error(format!(
+                "property '{}' must be of type {}",
+                key,
+                kinds.replace('|', " or ")
+            )),
+            Some(_) => {}
+            None if debugger.is_some() => error(format!(
+                "property '{}' is not allowed for type '{}'",
+                key, type_name
+            )),
+            None => {}
+        }
+    }
+
+    if let Some(debugger) = debugger
+        && request == Some("launch")
+    {
+        for key in &debugger.required {
+            if !map.contains_key(key) {
+                error(format!(
+                    "missing property '{}' required by type '{}'",
+                    key, type_name
+                ));
+            }
+        }
+        if !debugger.any_of.is_empty() && !debugger.any_of.iter().any(|k| map.contains_key(k)) {
+            error(format!(
+                "type '{}' needs one of the properties {}",
+                type_name,
+                debugger
+                    .any_of
+                    .iter()
+                    .map(|k| format!("'{}'", k))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn valid_configuration_has_no_errors() {
+        let config = json!({
+            "name": "Python",
+            "type": "debugpy",
+            "request": "launch",
+            "program": "${file}",
+            "args": "--verbose",
+            "presentation": { "group": "launch-gen" }
+        });
+        assert!(validate_config(&config).is_empty());
+    }
+
+    #[test]
+    fn unknown_property_of_known_type_is_reported() {
+        let config = json!({
+            "name": "Node",
+            "type": "node",
+            "request": "launch",
+            "program": "a.js",
+            "justMyCode": true
+        });
+        assert_eq!(
+            validate_config(&config),
+            ["'Node': property 'justMyCode' is not allowed for type 'node'"]
+        );
+    }
+
+    #[test]
+    fn wrong_value_kind_is_reported() {
+        let config = json!({
+            "name": "C++",
+            "type": "cppdbg",
+            "request": "launch",
+            "program": "a.out",
+            "args": "--flag",
+            "preLaunchTask": 1
+        });
+        assert_eq!(
+            validate_config(&config),
+            [
+                "'C++': property 'args' must be of type array",
+                "'C++': property 'preLaunchTask' must be of type string or object"
+            ]
+        );
+    }
+
+    #[test]
+    fn required_and_any_of_properties_are_checked_for_launch() {
+        let cpp = json!({ "name": "C++", "type": "cppdbg", "request": "launch" });
+        assert_eq!(
+            validate_config(&cpp),
+            ["'C++': missing property 'program' required by type 'cppdbg'"]
+        );
+
+        let python = json!({ "name": "Py", "type": "debugpy", "request": "launch" });
+        assert_eq!(
+            validate_config(&python),
+            ["'Py': type 'debugpy' needs one of the properties 'program', 'module', 'code'"]
+        );
+
+        let attach = json!({ "name": "Py", "type": "debugpy", "request": "attach" });
+        assert!(validate_config(&attach).is_empty());
+    }
+
+    #[test]
+    fn python_type_uses_the_debugpy_schema() {
+        let config = json!({
+            "name": "Flask",
+            "type": "python",
+            "request": "launch",
+            "module": "flask",
+            "jinja": "yes"
+        });
+        assert_eq!(
+            validate_config(&config),
+            ["'Flask': property 'jinja' must be of type boolean"]
+        );
+    }
+
+    #[test]
+    fn unknown_type_only_gets_common_checks() {
+        let config = json!({
+            "name": "Go",
+            "type": "go",
+            "request": "launch",
+            "mode": "auto",
+            "presentation": "hidden"
+        });
+        assert_eq!(
+            validate_config(&config),
+            ["'Go': property 'presentation' must be of type object"]
+        );
+
+        let incomplete = json!({ "type": "go", "request": "start" });
+        assert_eq!(
+            validate_config(&incomplete),
+            [
+                "'<unnamed>': missing required property 'name'",
+                "'<unnamed>': 'request' must be \"launch\" or \"attach\", not \"start\""
+            ]
+        );
+    }
+}